@@ -0,0 +1,72 @@
+use crate::ToDocError;
+use serde::Serialize;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// 诊断信息的级别
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Level {
+    Warning,
+    Error,
+}
+
+/// 处理过程中发现的一条警告或错误
+///
+/// code 是稳定的机器可读标识（如 `missing-example`），message 是给人看的说明
+#[derive(Debug, Clone, Serialize)]
+pub struct Diagnostic {
+    pub level  : Level,
+    pub file   : Option<PathBuf>,
+    pub line   : Option<usize>,
+    pub code   : &'static str,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn warning(file: &Path, code: &'static str, message: impl Into<String>) -> Self {
+        Diagnostic { level: Level::Warning, file: Some(file.to_path_buf()), line: None, code, message: message.into() }
+    }
+
+    pub fn error(file: &Path, code: &'static str, message: impl Into<String>) -> Self {
+        Diagnostic { level: Level::Error, file: Some(file.to_path_buf()), line: None, code, message: message.into() }
+    }
+
+    /// 不属于单个源文件的诊断信息，如扫描目录时跳过的条目、整次运行的汇总
+    pub fn global(level: Level, code: &'static str, message: impl Into<String>) -> Self {
+        Diagnostic { level, file: None, line: None, code, message: message.into() }
+    }
+
+    /// 附上行号（从 1 开始）
+    pub fn at_line(mut self, line: usize) -> Self {
+        self.line = Some(line);
+        self
+    }
+
+    /// 处理 file 时发生的 ToDocError
+    pub fn from_error(file: &Path, e: &ToDocError) -> Self {
+        Self::error(file, e.code(), e.to_string())
+    }
+
+    /// 单行 JSON（NDJSON 的一行），不含换行符
+    pub fn to_json_line(&self) -> String {
+        serde_json::to_string(self).expect("Diagnostic 总能序列化为 JSON")
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.level {
+            Level::Warning => write!(f, "警告: ")?,
+            Level::Error => write!(f, "错误: ")?,
+        }
+        if let Some(file) = &self.file {
+            write!(f, "{}:", file.display())?;
+            if let Some(line) = self.line {
+                write!(f, "{}:", line)?;
+            }
+            write!(f, " ")?;
+        }
+        write!(f, "{}", self.message)
+    }
+}
@@ -1,10 +1,16 @@
+use crate::FileDoc;
 use core::fmt;
 use once_cell::sync::Lazy;
 use regex::Regex;
+use serde::Serialize;
+use std::borrow::Cow;
+use std::collections::HashMap;
 use std::fs::File;
 use std::io::{BufRead, BufReader};
+use std::path::{Path, PathBuf};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum InputFileType {
     None,
     Lua,
@@ -12,18 +18,40 @@ pub enum InputFileType {
     Cpp,
     Rust,
     Python,
+    JavaScript,
+    TypeScript,
 }
 impl InputFileType {
+    #[allow(clippy::should_implement_trait)]
     pub fn from_str(s: &str) -> Option<Self> {
         match s {
             "lua" => Some(InputFileType::Lua),
-            "c" => Some(InputFileType::C),
-            "cpp" | "cc" => Some(InputFileType::Cpp),
-            "rs" => Some(InputFileType::Rust),
-            "py" => Some(InputFileType::Python),
+            "c" | "h" => Some(InputFileType::C),
+            "cpp" | "cc" | "cxx" | "c++" | "hpp" | "hh" | "hxx" => Some(InputFileType::Cpp),
+            "rs" | "rust" => Some(InputFileType::Rust),
+            "py" | "python" => Some(InputFileType::Python),
+            "js" | "jsx" | "mjs" | "cjs" | "javascript" => Some(InputFileType::JavaScript),
+            "ts" | "tsx" | "mts" | "cts" | "typescript" => Some(InputFileType::TypeScript),
             _ => None,
         }
     }
+    /// 按扩展名推断文件类型
+    pub fn from_path(path: &Path) -> Option<Self> {
+        path.extension().and_then(|e| e.to_str()).and_then(Self::from_str)
+    }
+    /// Markdown 代码块使用的语言名，None 为空
+    pub fn fence_name(&self) -> &'static str {
+        match self {
+            InputFileType::Lua => "lua",
+            InputFileType::C => "c",
+            InputFileType::Cpp => "cpp",
+            InputFileType::Rust => "rust",
+            InputFileType::Python => "python",
+            InputFileType::JavaScript => "javascript",
+            InputFileType::TypeScript => "typescript",
+            InputFileType::None => "",
+        }
+    }
     pub fn to_str(&self) -> Option<&'static str> {
         match self {
             InputFileType::Lua => Some("lua"),
@@ -31,13 +59,15 @@ impl InputFileType {
             InputFileType::Cpp => Some("cpp"),
             InputFileType::Rust => Some("rs"),
             InputFileType::Python => Some("py"),
+            InputFileType::JavaScript => Some("js"),
+            InputFileType::TypeScript => Some("ts"),
             InputFileType::None => Some("None"),
-            _ => None,
         }
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum FormulaType {
     Inline,
     Block,
@@ -48,33 +78,277 @@ pub enum OutputFileType {
 }
 
 /// 中间文档结构（简化）
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Parameter {
     pub name: String,
+    /// 在所属列表中的位置（从 0 开始）；列表重排后须调用 renumber 保持同步
     pub number: usize,
     pub description: String,
     pub type_name: String,
+    pub direction: Option<String>,
+    /// 描述之后的子标签内容（\list、\code 等），渲染为参数下方缩进的块
+    pub details: Vec<Description>,
+    /// EmmyLua 可选参数 `@param name? type`，name 中不含 `?`
+    pub optional: bool,
 }
 
-#[derive(Debug)]
+impl Parameter {
+    /// 第 number 个参数（从 0 开始），没有方向与子标签，不是可选参数
+    pub fn new(name: impl Into<String>, type_name: impl Into<String>, description: impl Into<String>, number: usize) -> Self {
+        Parameter {
+            name: name.into(),
+            number,
+            description: description.into(),
+            type_name: type_name.into(),
+            direction: None,
+            details: vec![],
+            optional: false,
+        }
+    }
+
+    /// 按当前顺序重新设置每个参数的 number
+    pub fn renumber(params: &mut [Parameter]) {
+        for (idx, p) in params.iter_mut().enumerate() {
+            p.number = idx;
+        }
+    }
+
+    /// 可变参数 / 可变数量的返回值 `...`
+    pub fn is_variadic(&self) -> bool {
+        self.name == "..."
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum DescriptionType {
     Text(String),
     Code(InputFileType, String),
     MathFormula(FormulaType, String),
     BulletList(i32, String),
     HTMLLink(String),
+    /// @note，与描述项一起按书写顺序保存
+    Note(String),
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Serialize)]
 pub struct Description {
     pub dtype: DescriptionType,
     pub content: String,
 }
+
+/// 解析后的函数（或字段）签名
+///
+/// 由语言解析器在创建文档块时构造一次，取名字、参数等信息时直接读取字段，
+/// 不再对签名文本做临时的字符串处理；raw 保留原始文本用于显示
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+pub struct Signature {
+    /// 原始签名文本
+    pub raw      : String,
+    /// 声明关键字：`function`、`local function`、`return function`、`local`，字段为空
+    pub keyword  : String,
+    /// 所属对象，`function A.b:c()` -> `A.b`
+    pub owner    : String,
+    /// 不含所属对象的名字，匿名函数为空
+    pub name     : String,
+    /// 参数名列表
+    pub params   : Vec<String>,
+    /// 签名中声明的返回类型（Lua 签名没有返回类型，始终为 None）
+    pub ret      : Option<String>,
+    /// 是否以 `:` 声明（带隐式 self 的方法）
+    pub is_method: bool,
+}
+
+impl Signature {
+    /// 解析 Lua 的函数声明（`function A:b(x)`、`local function f()`、`return function(...)`）
+    /// 或字段定义（`A.key = value`）
+    pub fn parse_lua(raw: &str) -> Signature {
+        let mut sig = Signature { raw: raw.to_string(), ..Default::default() };
+        let mut rest = raw.trim();
+        for keyword in ["local function", "return function", "function", "local"] {
+            if let Some(r) = rest.strip_prefix(keyword) {
+                if r.is_empty() || r.starts_with(|c: char| c.is_whitespace() || c == '(') {
+                    sig.keyword = keyword.to_string();
+                    rest = r.trim_start();
+                    break;
+                }
+            }
+        }
+
+        let name_end = if sig.keyword.ends_with("function") {
+            let open = rest.find('(').unwrap_or(rest.len());
+            let args = &rest[open..];
+            let args = args.strip_prefix('(').unwrap_or(args);
+            let close = args.find(')').unwrap_or(args.len());
+            sig.params = args[..close]
+                .split(',')
+                .map(|a| a.trim().to_string())
+                .filter(|a| !a.is_empty())
+                .collect();
+            open
+        } else {
+            rest.find('=').unwrap_or(rest.len())
+        };
+
+        let path = rest[..name_end].trim();
+        match path.rfind(['.', ':']) {
+            Some(idx) => {
+                sig.owner = path[..idx].to_string();
+                sig.name = path[idx + 1..].to_string();
+                sig.is_method = path[idx..].starts_with(':');
+            }
+            None => sig.name = path.to_string(),
+        }
+        sig
+    }
+
+    /// 解析 C 函数声明 `static char *name(const char *s, int n)`，返回类型放在 ret 中
+    /// 不是函数声明时返回 None
+    pub fn parse_c(raw: &str) -> Option<Signature> {
+        let caps = C_FUNCTION_RE.captures(raw.trim())?;
+        let ret = caps[1].trim();
+        if ret.is_empty() || ret.contains(['=', ';', '{', '}']) {
+            return None;
+        }
+        Some(Signature {
+            raw: raw.to_string(),
+            name: caps[2].to_string(),
+            params: CFileParser::parse_params(&caps[3]).into_iter().map(|(name, _)| name).collect(),
+            ret: Some(ret.to_string()),
+            ..Default::default()
+        })
+    }
+
+    /// 解析 C++ 函数声明 `template <typename T> static T ns::Box<T>::get(int i = 0) const`：
+    /// 返回类型（去掉 static 等说明符）放在 ret 中，构造与析构函数没有返回类型，
+    /// `::` 限定的前缀放在 owner 中。不是函数声明时返回 None
+    pub fn parse_cpp(raw: &str) -> Option<Signature> {
+        let (_, decl) = CppFileParser::strip_template(raw.trim());
+        let (head, params, tail) = CppFileParser::split_function(decl)?;
+        let (ret, path) = CppFileParser::split_name(head)?;
+        if ret.contains(['=', ';', '{', '}', '(']) || path.ends_with(':') {
+            return None;
+        }
+        let ret = CppFileParser::strip_specifiers(ret);
+        let ret = match tail.split_once("->") {
+            Some((_, trailing)) if ret == "auto" => Some(CppFileParser::strip_default(trailing).0.to_string()),
+            _ => (!ret.is_empty()).then_some(ret),
+        };
+        let (owner, name) = match CppFileParser::rfind_scope(path) {
+            Some(i) => (&path[..i], &path[i + 2..]),
+            None => ("", path),
+        };
+        Some(Signature {
+            raw: raw.to_string(),
+            owner: owner.to_string(),
+            name: name.to_string(),
+            params: CppFileParser::parse_params(params).into_iter().map(|(name, _, _)| name).collect(),
+            ret,
+            ..Default::default()
+        })
+    }
+
+    /// 模块以 `return function(...)` 导出的匿名函数，或没有名字的模块文档（Rust 的 `//!`）
+    pub fn is_anonymous(&self) -> bool {
+        self.keyword == "return function" || (self.keyword == "mod" && self.name.is_empty())
+    }
+
+    /// 解析 Rust 声明 `pub fn name(a: T, b: U) -> R` / `pub struct Name` / `enum Name`
+    pub fn parse_rust(raw: &str) -> Signature {
+        let mut sig = Signature { raw: raw.to_string(), ..Default::default() };
+        let mut rest = RustFileParser::strip_qualifiers(raw.trim());
+        for keyword in ["fn", "struct", "enum"] {
+            if let Some(r) = rest.strip_prefix(keyword).filter(|r| r.starts_with(char::is_whitespace)) {
+                sig.keyword = keyword.to_string();
+                rest = r.trim_start();
+                break;
+            }
+        }
+        let name_end = rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len());
+        sig.name = rest[..name_end].to_string();
+        if sig.keyword != "fn" {
+            return sig;
+        }
+
+        let (open, close) = match rest.find('(').zip(rest.rfind(')')) {
+            Some((open, close)) if open < close => (open, close),
+            _ => return sig,
+        };
+        sig.params = RustFileParser::parse_params(&rest[open + 1..close]).into_iter().map(|(name, _)| name).collect();
+        if let Some(ret) = rest[close + 1..].trim().strip_prefix("->") {
+            let ret = ret.split(" where ").next().unwrap_or(ret).trim();
+            sig.ret = Some(ret.to_string());
+        }
+        sig
+    }
+
+    /// 解析 Python 声明 `def name(a: int, b=1) -> str` / `async def` / `class Name(Base)`
+    /// `self`、`cls` 不算参数，返回注解放在 ret 中
+    pub fn parse_python(raw: &str) -> Signature {
+        let mut sig = Signature { raw: raw.to_string(), ..Default::default() };
+        let mut rest = raw.trim();
+        for keyword in ["async def", "def", "class"] {
+            if let Some(r) = rest.strip_prefix(keyword).filter(|r| r.starts_with(char::is_whitespace)) {
+                sig.keyword = keyword.to_string();
+                rest = r.trim_start();
+                break;
+            }
+        }
+        let name_end = rest.find(|c: char| !(c.is_alphanumeric() || c == '_')).unwrap_or(rest.len());
+        sig.name = rest[..name_end].to_string();
+        if sig.keyword == "class" {
+            return sig;
+        }
+
+        let (open, close) = match rest.find('(').zip(rest.rfind(')')) {
+            Some((open, close)) if open < close => (open, close),
+            _ => return sig,
+        };
+        sig.params = PythonFileParser::parse_params(&rest[open + 1..close])
+            .into_iter()
+            .map(|(name, _)| name)
+            .filter(|name| name != "self" && name != "cls")
+            .collect();
+        if let Some(ret) = rest[close + 1..].trim().strip_prefix("->") {
+            sig.ret = Some(ret.trim().to_string());
+        }
+        sig
+    }
+
+    /// 带所属对象的完整名字，例如 `A.sub`、`A:new`
+    pub fn qualified_name(&self) -> String {
+        if self.owner.is_empty() {
+            return self.name.clone();
+        }
+        let sep = if self.is_method { ':' } else { '.' };
+        format!("{}{}{}", self.owner, sep, self.name)
+    }
+
+    /// 是否是所属对象的成员函数：以 `:` 声明，或第一个参数就是所属对象本身
+    pub fn is_member(&self) -> bool {
+        self.is_method || (!self.owner.is_empty() && self.params.first() == Some(&self.owner))
+    }
+}
+
+/// 解析文档注释时发现的问题，由调用方转为诊断信息
+///
+/// code 是稳定的机器可读标识（如 `unknown-tag`），message 是给人看的说明
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseWarning {
+    pub code   : &'static str,
+    pub message: String,
+}
+
 /**
  * @brief 这是一个示例函数      (brief)
  * @param x number 第一个参数  (Parameter: name, type_name, description)
  * @param y number 第二个参数  (Parameter: name, type_name, description)
  * @return number 返回值说明   (Parameter: "", type_name, description)
+ * @yields number 产出值说明   (Parameter: "", type_name, description)，可重复
+ * @todo 待办事项               (todos)，可重复
+ * @see Module.other_fn        (see)，可重复
+ * @generic T, K : Base         (generics)，可重复
+ * @throws Type 错误条件       (errors)，可重复；@error 错误条件 不带类型
  * @includes <xxx>, <xxx>
  * @description
  *     \text text  (DescriptionType.Text)
@@ -86,23 +360,154 @@ pub struct Description {
  *     \html url   (DescriptionType.HTMLLink)
  * function signature (x, y) (signature)
  */
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct DocBlock {
-    pub signature   : String,
+    pub signature   : Signature,
     pub brief       : String,
     pub note        : String,
     pub includes    : Vec<String>,
     pub parameters  : Vec<Parameter>,
     pub descriptions: Vec<Description>,
-    pub ret_value   : Option<Parameter>,
+    /// 返回值（@return），Lua 函数可以有多个
+    pub ret_value   : Vec<Parameter>,
     pub owner_object: String,
     pub is_local    : bool,
     pub is_member   : bool,
+    pub since       : Option<String>,
+    pub complexity  : Option<String>,
+    pub is_async    : bool,
+    pub lang        : InputFileType,
+    pub example     : Option<String>,
+    pub example_file: Option<String>,
+    pub properties  : Vec<Parameter>,
+    pub aliases     : Vec<String>,
+    /// 所属的导出模块（`return M` 返回的表），None 表示不是模块的公开 API
+    pub module      : Option<String>,
+    /// 协程/生成器每次产出的值（@yields）
+    pub yields      : Vec<Parameter>,
+    /// 所属分组（@group，或源码中的分节横幅注释）
+    pub group       : Option<String>,
+    /// 结构体/枚举的字段
+    pub fields      : Vec<Parameter>,
+    /// 待办事项（@todo），可重复
+    pub todos       : Vec<String>,
+    /// 声明的类名（@class），该类的成员函数在输出中归到同一节
+    pub class       : Option<String>,
+    /// 已弃用（@deprecated）时为弃用说明，没有说明时为空字符串
+    pub deprecated  : Option<String>,
+    /// 相关函数的引用（@see），可重复
+    pub see         : Vec<String>,
+    /// 可能抛出的错误（@throws / @error），可重复；没有错误类型时 type_name 为空
+    pub errors      : Vec<Parameter>,
+    /// 类型参数（@generic），约束写作 `K: Base`
+    pub generics    : Vec<String>,
+    /// `:` 定义的方法隐含的 self 参数，类型默认为所属对象；不出现在 parameters 中
+    pub receiver    : Option<Parameter>,
+    /// 作者（@author），多行 @author 以 `, ` 连接
+    pub author      : Option<String>,
+    /// 版本（@version）
+    pub version     : Option<String>,
+    /// 文档注释第一行的行号（从 1 开始），0 表示解析器没有记录位置
+    pub start_line  : usize,
+    /// 被文档化的声明结束处（多行声明的最后一行）的行号，0 表示未记录
+    pub end_line    : usize,
+    /// 解析文档注释时发现的问题（未知的标签等），不属于文档内容
+    #[serde(skip)]
+    pub warnings    : Vec<ParseWarning>,
+}
+
+impl DocBlock {
+    /// 创建一个空的文档块
+    pub fn new(lang: InputFileType) -> DocBlock {
+        DocBlock {
+            signature   : Signature::default(),
+            brief       : String::new(),
+            note        : String::new(),
+            includes    : vec![],
+            parameters  : vec![],
+            descriptions: vec![],
+            ret_value   : vec![],
+            owner_object: String::new(),
+            is_local    : false,
+            is_member   : false,
+            since       : None,
+            complexity  : None,
+            is_async    : false,
+            lang,
+            example     : None,
+            example_file: None,
+            properties  : vec![],
+            aliases     : vec![],
+            module      : None,
+            yields      : vec![],
+            group       : None,
+            fields      : vec![],
+            todos       : vec![],
+            class       : None,
+            deprecated  : None,
+            see         : vec![],
+            errors      : vec![],
+            generics    : vec![],
+            receiver    : None,
+            author      : None,
+            version     : None,
+            start_line  : 0,
+            end_line    : 0,
+            warnings    : vec![],
+        }
+    }
+
+    /// 从签名中提取函数（或字段）名，例如 `function A.sub(x)` -> `A.sub`
+    /// 匿名导出函数 `return function(...)` 使用其所属的模块名
+    pub fn function_name(&self) -> String {
+        if self.signature.is_anonymous() {
+            return self.owner_object.clone();
+        }
+        // C++ 的限定名用 `::` 连接
+        if self.lang == InputFileType::Cpp && !self.signature.owner.is_empty() {
+            return format!("{}::{}", self.signature.owner, self.signature.name);
+        }
+        self.signature.qualified_name()
+    }
+
+    /// 记录一条解析警告
+    pub fn warn(&mut self, code: &'static str, message: impl Into<String>) {
+        self.warnings.push(ParseWarning { code, message: message.into() });
+    }
+
+    /// 签名中的参数名，例如 `function f(a, b)` -> ["a", "b"]
+    pub fn signature_args(&self) -> Vec<String> {
+        self.signature.params.clone()
+    }
+
+    /// 按签名中的参数顺序重排 parameters
+    /// 签名中存在但未写 @param 的参数按其位置补上（类型与描述为空），
+    /// 写了 @param 但签名中不存在的参数排在最后；两种情况都以提示信息返回
+    pub fn sort_params_by_signature(&mut self) -> Vec<String> {
+        let mut notes = Vec::new();
+        let mut documented = std::mem::take(&mut self.parameters);
+        for (idx, arg) in self.signature_args().into_iter().enumerate() {
+            match documented.iter().position(|p| p.name == arg) {
+                Some(pos) => self.parameters.push(documented.remove(pos)),
+                None => {
+                    notes.push(format!("参数 {} 没有对应的 @param 文档", arg));
+                    self.parameters.push(Parameter::new(arg, "", "", idx));
+                }
+            }
+        }
+        for p in documented {
+            notes.push(format!("@param {} 不在函数签名中", p.name));
+            self.parameters.push(p);
+        }
+        Parameter::renumber(&mut self.parameters);
+        notes
+    }
 }
 
 impl std::fmt::Display for DocBlock {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        writeln!(f, "Signature: {}", self.signature)?;
+        writeln!(f, "Signature: {}", self.signature.raw)?;
+        writeln!(f, "Lines: {}-{}", self.start_line, self.end_line)?;
         writeln!(f, "Brief: {}", self.brief)?;
         writeln!(f, "Note: {}", self.note)?;
         writeln!(f, "Includes: {:?}", self.includes)?;
@@ -114,7 +519,7 @@ impl std::fmt::Display for DocBlock {
                 p.name, p.type_name, p.description
             )?;
         }
-        if let Some(ret) = &self.ret_value {
+        for ret in &self.ret_value {
             writeln!(
                 f,
                 "Return: {} ({})",
@@ -130,33 +535,211 @@ impl std::fmt::Display for DocBlock {
 }
 
 /// 解析器 trait：把文件解析成一组 DocBlock（中间结构）
+///
+/// 各语言只需实现 parse_reader；parse / parse_str 是读取文件与内存字符串的便捷入口
 pub trait FileParser {
-    fn parse(&self, file: &File) -> Vec<DocBlock>;
+    fn parse_reader(&self, reader: &mut dyn BufRead) -> Vec<DocBlock>;
+
+    fn parse(&self, file: &File) -> Vec<DocBlock> {
+        self.parse_reader(&mut BufReader::new(file))
+    }
+
+    fn parse_str(&self, s: &str) -> Vec<DocBlock> {
+        self.parse_reader(&mut s.as_bytes())
+    }
 }
 
-/// 输出格式化器 trait：把 DocBlock 转为目标格式字符串
+/// 输出格式化器 trait：把一个输出文件中的所有 DocBlock 转为目标格式字符串
+///
+/// 按切片而不是单个文档块格式化，目录、分组等文件级内容也由格式化器负责；
+/// 失败时返回错误描述
 pub trait OutputFileFormatter {
-    fn format(&self, content: &DocBlock) -> String;
+    fn format(&self, content: &[DocBlock]) -> Result<String, String>;
+
+    /// 格式化从 source 源文件解析出的文档块；需要源文件信息（如 front-matter 标题）的格式化器覆盖此方法
+    fn format_file(&self, source: &Path, content: &[DocBlock]) -> Result<String, String> {
+        let _ = source;
+        self.format(content)
+    }
+}
+
+/// 为没有名字的文档块补上模块名（通常为文件名），与语言无关
+///
+/// Lua 的 `return function(...)` 匿名导出函数以模块名作为所属对象；
+/// Rust 的 `//!` 模块文档块以模块名作为名字
+pub fn name_anonymous_blocks(blocks: &mut [DocBlock], module_name: &str) {
+    for block in blocks.iter_mut() {
+        if !block.signature.is_anonymous() {
+            continue;
+        }
+        block.owner_object = module_name.to_string();
+        if block.signature.keyword == "mod" {
+            // 模块文档本身不是导出的 API，只补上名字
+            block.signature.raw = format!("mod {}", module_name);
+        } else {
+            block.module = Some(module_name.to_string());
+        }
+    }
 }
 
 fn is_space_line(line: &str) -> bool {
-    for c in line.chars() {
-        if !c.is_whitespace() {
-            return false;
+    line.chars().all(|c| c.is_whitespace())
+}
+/// 匹配表构造器赋值语句的开头: `local M = {` / `M.sub = {`
+static TABLE_LITERAL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:local\s+)?([A-Za-z_][\w.]*)\s*=\s*\{").unwrap()
+});
+
+/// 匹配模块末尾导出表的语句: `return M` / `return setmetatable(M, mt)`
+static MODULE_RETURN_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^return\s+(?:setmetatable\s*\(\s*([A-Za-z_]\w*)\s*[,)]|([A-Za-z_]\w*)\s*;?\s*$)").unwrap()
+});
+
+/// 分节横幅注释前后至少需要的 `-` 个数
+const BANNER_MIN_DASHES: usize = 4;
+
+/// 匹配分节横幅注释: `---------- Section: Networking ----------`
+static BANNER_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(&format!(
+        r"^\s*-{{{n},}}\s*(?i:section\s*:\s*)?([^-\s@][^-]*?)\s*-{{{n},}}\s*$",
+        n = BANNER_MIN_DASHES
+    ))
+    .unwrap()
+});
+
+/// 匹配表构造器中的 `key = value` 项
+static TABLE_KEY_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)^([A-Za-z_]\w*)\s*=\s*(.*)$").unwrap()
+});
+
+/// 表构造器中的一项：紧贴其上方的文档注释 + 该项的源码
+struct TableEntry {
+    comments: Vec<String>,
+    source: String,
+    /// 第一行文档注释与该项本身在表内容中的行偏移（从 0 开始）
+    doc_line: usize,
+    line: usize,
+}
+
+/// 去掉标记两侧的反引号：`` `number` `` -> `number`
+fn strip_backticks(token: &str) -> &str {
+    token
+        .strip_prefix('`')
+        .and_then(|t| t.strip_suffix('`'))
+        .unwrap_or(token)
+}
+
+/// 把标签行（已去掉 `@`）拆分为 (标签名, 方括号参数, 正文)
+/// 例如 `param[in, out] x int desc` -> ("param", Some("in, out"), "x int desc")
+fn split_tag(content: &str) -> (&str, Option<&str>, &str) {
+    let ws = content.find(char::is_whitespace).unwrap_or(content.len());
+    if let Some(open) = content[..ws].find('[') {
+        if let Some(close) = content[open..].find(']') {
+            let close = open + close;
+            return (&content[..open], Some(content[open + 1..close].trim()), content[close + 1..].trim());
+        }
+    }
+    (&content[..ws], None, content[ws..].trim())
+}
+
+/// 从开头切出一个类型，返回 (类型, 之后的描述)；描述中的空白合并为一个空格
+/// 括号内的空白不结束类型，函数类型的返回值与联合类型也算在类型内：
+/// `fun(item: T): U desc` -> ("fun(item: T): U", "desc")，`string | nil desc` -> ("string | nil", "desc")
+fn split_type(text: &str) -> (&str, String) {
+    let text = text.trim();
+    let mut depth = 0i32;
+    let mut end = text.len();
+    for (i, c) in text.char_indices() {
+        match c {
+            '(' | '<' | '[' | '{' => depth += 1,
+            ')' | '>' | ']' | '}' => depth -= 1,
+            c if c.is_whitespace() && depth <= 0 => {
+                let (before, after) = (text[..i].trim_end(), text[i..].trim_start());
+                if !(before.ends_with("):") || before.ends_with('|') || after.starts_with('|')) {
+                    end = i;
+                    break;
+                }
+            }
+            _ => {}
         }
     }
-    return true;
+    let description = text[end..].split_whitespace().collect::<Vec<_>>().join(" ");
+    (strip_backticks(&text[..end]), description)
+}
+
+/// 解析 `name type desc` 形式的标签内容（@param / @property 等），`name?` 表示可选
+fn parse_parameter(body: &str, number: usize) -> Option<Parameter> {
+    let (name, rest) = body.trim().split_once(char::is_whitespace)?;
+    let (type_name, description) = split_type(rest);
+    if type_name.is_empty() {
+        return None;
+    }
+    let name = strip_backticks(name);
+    let (name, optional) = match name.strip_suffix('?') {
+        Some(name) => (name, true),
+        None => (name, false),
+    };
+    Some(Parameter { optional, ..Parameter::new(name, type_name, description, number) })
+}
+
+/// 解析一行 `\subtag body` 子标签，未知的子标签返回 None
+/// `\code{python}` 指定代码块的语言，未指定或无法识别时与源文件语言一致，`\code{text}` 为不标语言的代码块；
+/// `\formula{block}` 为独立成行的公式，其余为行内公式
+fn parse_subtag(content: &str, lang: InputFileType) -> Option<Description> {
+    let parts: Vec<&str> = content.splitn(2, |c: char| c.is_whitespace()).collect();
+    let subtag = &parts[0][1..]; // skip '\'
+    let body = if parts.len() > 1 { parts[1].trim() } else { "" };
+
+    // 可选的 `{参数}`：\code 的语言、\formula 的 block；无法识别时使用默认值
+    let (subtag, arg) = match subtag.split_once('{') {
+        Some((name, arg)) => (name, arg.strip_suffix('}').map(str::trim)),
+        None => (subtag, None),
+    };
+    let dtype = match subtag {
+        "text" => DescriptionType::Text(body.to_string()),
+        "code" => {
+            let code_lang = match arg {
+                Some("text") => InputFileType::None,
+                arg => arg.and_then(InputFileType::from_str).unwrap_or(lang),
+            };
+            DescriptionType::Code(code_lang, body.to_string())
+        }
+        "formula" => {
+            let ftype = if arg == Some("block") { FormulaType::Block } else { FormulaType::Inline };
+            DescriptionType::MathFormula(ftype, body.to_string())
+        }
+        "list" => DescriptionType::BulletList(0, body.to_string()),
+        "html" => DescriptionType::HTMLLink(body.to_string()),
+        _ => return None,
+    };
+    Some(Description { dtype, content: body.to_string() })
 }
+
 pub struct LuaFileParser {}
 impl LuaFileParser {
-    const ANNOTATION: &'static str = "-- ";
+    /// 去掉行首的注释标记，返回注释内容；不是注释行时返回 None
+    /// 行首的任意空格/制表符混合缩进都会被忽略，`--` 与 `---` 两种标记等价：
+    /// `\t  ---@brief x` -> `@brief x`，`--   - item` -> `- item`
+    pub fn strip_lua_comment_leader(line: &str) -> Option<&str> {
+        let rest = line.trim_start().strip_prefix("--")?;
+        let rest = rest.strip_prefix('-').unwrap_or(rest);
+        Some(rest.trim())
+    }
 
     pub fn is_annotation_line(line: &str) -> bool {
-        line.trim_start().starts_with(Self::ANNOTATION)
+        Self::strip_lua_comment_leader(line).is_some()
     }
 
+    /// 函数声明是否在这一行结束：去掉行尾注释、空白与 `;` 后以 `)` 或关键字 `end` 结尾
     pub fn is_api_tail(line: &str) -> bool {
-        return line.ends_with(")") || line.trim_end().ends_with("end");
+        let code = Self::remove_annotation(line);
+        let code = code.trim_end().trim_end_matches(';').trim_end();
+        if code.ends_with(')') {
+            return true;
+        }
+        // `end` 须是独立的关键字，`function send` 不算
+        code.strip_suffix("end")
+            .is_some_and(|rest| !rest.ends_with(|c: char| c.is_alphanumeric() || c == '_'))
     }
 
     pub fn remove_annotation(line: &str) -> String {
@@ -166,7 +749,6 @@ impl LuaFileParser {
         let mut i = 0usize;
         let mut in_squote = false;
         let mut in_dquote = false;
-        let mut in_longstring_level: Option<usize> = None;
 
         while i < n {
             if in_squote {
@@ -199,28 +781,6 @@ impl LuaFileParser {
                 continue;
             }
 
-            if let Some(level) = in_longstring_level {
-                if chars[i] == ']' {
-                    let mut j = i + 1;
-                    let mut eq = 0usize;
-                    while j < n && chars[j] == '=' {
-                        eq += 1;
-                        j += 1;
-                    }
-                    if eq == level && j < n && chars[j] == ']' {
-                        for t in i..=j {
-                            out.push(chars[t]);
-                        }
-                        i = j + 1;
-                        in_longstring_level = None;
-                        continue;
-                    }
-                }
-                out.push(chars[i]);
-                i += 1;
-                continue;
-            }
-
             let c = chars[i];
             if c == '\'' {
                 in_squote = true;
@@ -235,61 +795,24 @@ impl LuaFileParser {
                 continue;
             }
 
-            if c == '[' {
-                let mut j = i + 1;
-                let mut eq = 0usize;
-                while j < n && chars[j] == '=' {
-                    eq += 1;
-                    j += 1;
-                }
-                if j < n && chars[j] == '[' {
-                    in_longstring_level = Some(eq);
-                    for t in i..=j {
-                        out.push(chars[t]);
-                    }
-                    i = j + 1;
-                    continue;
-                }
+            // 长字符串 `[[ ... ]]` / `[==[ ... ]==]` 原样保留，其中的 `--` 不是注释
+            if let Some((level, body)) = Self::long_bracket_open(&chars, i) {
+                let after = Self::long_bracket_close(&chars, body, level).map_or(n, |(_, after)| after);
+                out.extend(&chars[i..after]);
+                i = after;
+                continue;
             }
 
-            // detect comment start '--'
+            // detect comment start '--'：行内闭合的块注释 `--[[ ... ]]` 跳过，其余注释到行尾
             if c == '-' && i + 1 < n && chars[i + 1] == '-' {
-                if i + 2 < n && chars[i + 2] == '[' {
-                    let mut j = i + 3;
-                    let mut eq = 0usize;
-                    while j < n && chars[j] == '=' {
-                        eq += 1;
-                        j += 1;
-                    }
-                    if j < n && chars[j] == '[' {
-                        let mut k = j + 1;
-                        let mut found = None;
-                        while k < n {
-                            if chars[k] == ']' {
-                                let mut m = k + 1;
-                                let mut eq2 = 0usize;
-                                while m < n && chars[m] == '=' {
-                                    eq2 += 1;
-                                    m += 1;
-                                }
-                                if eq2 == eq && m < n && chars[m] == ']' {
-                                    found = Some(m);
-                                    break;
-                                }
-                            }
-                            k += 1;
-                        }
-                        if let Some(endpos) = found {
-                            i = endpos + 1;
-                            continue;
-                        } else {
-                            break;
-                        }
-                    } else {
-                        break;
+                let comment_end = Self::long_bracket_open(&chars, i + 2)
+                    .and_then(|(level, body)| Self::long_bracket_close(&chars, body, level));
+                match comment_end {
+                    Some((_, after)) => {
+                        i = after;
+                        continue;
                     }
-                } else {
-                    break;
+                    None => break,
                 }
             }
             out.push(c);
@@ -299,184 +822,717 @@ impl LuaFileParser {
         out.trim_start().trim_end().to_string()
     }
 
-    pub fn is_doc_comment(line: &str) -> bool {
-        let t = line.trim_start();
-        return t.starts_with("---@") || t.starts_with("--@") || t.starts_with("-- @");
+    /// 模块以 `return function(...) ... end` 导出的匿名函数
+    pub fn is_anonymous_module_function(line: &str) -> bool {
+        line.trim_start().starts_with("return function")
     }
 
-    pub fn extract_owner_object(line: &str) -> String {
-        let line = line.trim();
-        let s = if line.starts_with("local function") {
-            return "local".to_string();
-        } else if line.starts_with("function") {
-            &line[8..]
-        } else {
-            return String::new();
-        };
+    /// 分节横幅注释的标题，例如 `---- Section: Networking ----` -> `Networking`
+    pub fn banner_label(line: &str) -> Option<String> {
+        BANNER_RE.captures(line).map(|caps| caps[1].to_string())
+    }
 
-        let s = s.trim_start();
-        if let Some(idx) = s.find(|c| c == '.' || c == ':') {
-            return s[..idx].trim().to_string();
+    /// 为尚未指定分组的文档块设置横幅注释给出的分组
+    fn apply_banner_group(blocks: &mut [DocBlock], group: &Option<String>) {
+        for block in blocks.iter_mut().filter(|b| b.group.is_none()) {
+            block.group = group.clone();
         }
-        String::new()
     }
 
-    pub fn is_member_function(line: &str, obj_name: &str) -> bool {
-        return if line.find(":").is_some() == false {
-            let dot_idx = line.find(".");
-            if dot_idx.is_some() {
-                let left_bracket_idx = line.find("(").unwrap();
-                let right_bracket_idx = line.find(")").unwrap();
-                let sub_params_list = &line[left_bracket_idx..right_bracket_idx];
-                let first_comma_idx = sub_params_list.find(",");
-                if first_comma_idx.is_some() {
-                    // 有参数列表的情况下 拿到第一个参数
-                    let first_param = &sub_params_list[1..first_comma_idx.unwrap()].trim();
-                    if *first_param == obj_name { true } else { false }
-                }else{
-                    // 一个参数就是table name, function A.function(A) 也是一个成员函数
-                    if sub_params_list.trim().len() > 0 && sub_params_list == obj_name { true }else{ false }
-                }
-            }else{
-                false
+    /// 顶层（不缩进）的 `return M` / `return setmetatable(M, ...)` 语句导出的表名
+    pub fn module_return(line: &str) -> Option<String> {
+        let caps = MODULE_RETURN_RE.captures(line.trim_end())?;
+        caps.get(1).or_else(|| caps.get(2)).map(|m| m.as_str().to_string())
+    }
+
+    /// 把导出表（及其子表）上的函数和字段标记为该模块的公开 API
+    pub fn mark_exported(blocks: &mut [DocBlock], table: &str) {
+        let nested = format!("{}.", table);
+        for block in blocks.iter_mut() {
+            let owner = &block.signature.owner;
+            let is_table = owner.is_empty() && block.signature.name == table;
+            if is_table || owner == table || owner.starts_with(&nested) {
+                block.module = Some(table.to_string());
+                block.is_local = false;
             }
+        }
+    }
+
+    /// 使用 `---` 注释标记的行（LuaCATS / EmmyLua 的文档注释写法）
+    fn is_triple_dash(line: &str) -> bool {
+        line.trim_start().starts_with("---")
+    }
+
+    pub fn is_doc_comment(line: &str) -> bool {
+        Self::strip_lua_comment_leader(line).is_some_and(|c| c.starts_with('@'))
+    }
+
+    /// function A:f() 或第一个参数为 obj_name 的 function A.f(A) 是成员函数
+    pub fn is_member_function(line: &str, obj_name: &str) -> bool {
+        let sig = Signature::parse_lua(line);
+        sig.is_method || sig.params.first().is_some_and(|p| p == obj_name)
+    }
+    /// 若 chars[i..] 以长括号 `[[` / `[==[` 开头，返回 (等号个数, 内容起始位置)
+    fn long_bracket_open(chars: &[char], i: usize) -> Option<(usize, usize)> {
+        if i >= chars.len() || chars[i] != '[' {
+            return None;
+        }
+        let mut j = i + 1;
+        while j < chars.len() && chars[j] == '=' {
+            j += 1;
+        }
+        if j < chars.len() && chars[j] == '[' {
+            Some((j - i - 1, j + 1))
         } else {
-            true
-        };
+            None
+        }
     }
-    /// 解析并创建一个 DocBlock
-    /// 这里采用了两层解析结构：
-    /// 1. 第一层：识别 @tag
-    /// 2. 第二层：如果处于 @description 下，识别 \subtag
-    pub fn create_docblock(buf: Vec<String>) -> DocBlock {
-        for str in &buf {
-            println!("Doc Line: {}", str);
+
+    /// 若 line 以块注释 `--[[` / `--[==[` 开头，返回 (等号个数, 本行的注释内容, 是否在本行闭合)
+    fn block_comment_open(line: &str) -> Option<(usize, String, bool)> {
+        let chars: Vec<char> = line.trim_start().chars().collect();
+        if chars.len() < 2 || chars[0] != '-' || chars[1] != '-' {
+            return None;
         }
-        let mut block = DocBlock {
-            signature   : String::new(),
-            brief       : String::new(),
-            note        : String::new(),
-            includes    : vec![],
-            parameters  : vec![],
-            descriptions: vec![],
-            ret_value   : None,
-            owner_object: String::new(),
-            is_local    : false,
-            is_member   : false,
-        };
+        let (level, start) = Self::long_bracket_open(&chars, 2)?;
+        let (text, closed) = Self::block_comment_line(&chars[start..], level);
+        Some((level, text, closed))
+    }
 
-        // 简单的状态机，用于处理多行内容（例如 description 下的子标签）
-        let mut current_tag = String::new();
+    /// 块注释内部的一行：返回等级为 level 的结尾之前的内容，以及是否遇到了结尾
+    fn block_comment_line(chars: &[char], level: usize) -> (String, bool) {
+        match Self::long_bracket_close(chars, 0, level) {
+            Some((end, _)) => (chars[..end].iter().collect(), true),
+            None => (chars.iter().collect(), false),
+        }
+    }
 
-        for line in buf {
-            // 1. 清理注释符号，获取纯文本内容
-            // 简单实现：找到第一个 @ 或者 \ 之前的部分作为前缀去除，或者直接去除 --
-            // 实际工程中建议用正则或精确匹配
-            let content = if let Some(idx) = line.find("@") {
-                &line[idx..]
-            } else if let Some(idx) = line.find("\\") {
-                &line[idx..]
-            } else {
-                let t = line.trim_start();
-                if t.starts_with("--") {
-                    t.trim_start_matches('-').trim()
-                } else {
-                    t
+    /// 从 start 开始查找等级为 level 的长括号结尾，返回 (内容结束位置, 结尾之后的位置)
+    fn long_bracket_close(chars: &[char], start: usize, level: usize) -> Option<(usize, usize)> {
+        let n = chars.len();
+        let mut k = start;
+        while k < n {
+            if chars[k] == ']' {
+                let mut m = k + 1;
+                let mut eq = 0usize;
+                while m < n && chars[m] == '=' {
+                    eq += 1;
+                    m += 1;
                 }
-            };
-            
-            // 2. 解析主标签 @xxx
-            if content.starts_with("@") {
-                let parts: Vec<&str> = content.splitn(2, |c: char| c.is_whitespace()).collect();
-                let tag = &parts[0][1..]; // skip '@'
-                let body = if parts.len() > 1 { parts[1].trim() } else { "" };
-                
-                current_tag = tag.to_string();
-
-                match tag {
-                    "brief" => block.brief = body.to_string(),
-                    "param" => {
-                        // 解析 param: name type desc
-                        let p_parts: Vec<&str> = body.split_whitespace().collect();
-                        if p_parts.len() >= 2 {
-                            block.parameters.push(Parameter {
-                                name: p_parts[0].to_string(),
-                                type_name: p_parts[1].to_string(),
-                                number: block.parameters.len(),
-                                description: p_parts[2..].join(" "),
-                            });
-                        }
-                    }
-                    "return" => {
-                         let p_parts: Vec<&str> = body.split_whitespace().collect();
-                         if !p_parts.is_empty() {
-                            block.ret_value = Some(Parameter {
-                                name: "".to_string(),
-                                type_name: p_parts[0].to_string(),
-                                number: 0,
-                                description: p_parts[1..].join(" "),
-                            });
-                         }
-                    }
-                    "includes" => {
-                        // 简单逗号分隔
-                        for inc in body.split(',') {
-                            block.includes.push(inc.trim().to_string());
-                        }
-                    }
-                    "note" => block.note = body.to_string(),
-                    "description" => {
-                        // 进入 description 模式，后续行可能包含 \text 等
-                    }
-                    _ => {
-                        println!("Unknown tag: {}", tag);
-                    }
+                if eq == level && m < n && chars[m] == ']' {
+                    return Some((k, m + 1));
                 }
-            } else if content.starts_with("\\") {
-                 // 3. 解析子标签 (仅当在 description 下，或者设计为全局可用)
-                 // 为了扩展性，这里可以进一步封装成 parse_description_line(content)
-                if current_tag == "description" {
-                    let parts: Vec<&str> = content.splitn(2, |c: char| c.is_whitespace()).collect();
-                    let subtag = &parts[0][1..]; // skip '\'
-                    let body = if parts.len() > 1 { parts[1].trim() } else { "" };
-                    
-                    let desc_type = match subtag {
-                        "text" => Some(DescriptionType::Text(body.to_string())),
-                        "code" => Some(DescriptionType::Code(InputFileType::None, body.to_string())), // 需要更复杂的解析来支持 code{lua}
-                        "formula" => Some(DescriptionType::MathFormula(FormulaType::Inline, body.to_string())),
-                        "list" => Some(DescriptionType::BulletList(0, body.to_string())),
-                        "html" => Some(DescriptionType::HTMLLink(body.to_string())),
-                        _ => None,
-                    };
+            }
+            k += 1;
+        }
+        None
+    }
 
-                    if let Some(dt) = desc_type {
-                        block.descriptions.push(Description {
-                            dtype: dt,
-                            content: body.to_string(),
-                        });
-                    }
+    /// 若 chars[i] 处是字符串、注释或长字符串的开头，返回跳过它之后的位置
+    fn skip_string_or_comment(chars: &[char], i: usize) -> Option<usize> {
+        let n = chars.len();
+        let c = chars[i];
+        if c == '\'' || c == '"' {
+            let mut j = i + 1;
+            while j < n && chars[j] != c {
+                if chars[j] == '\\' {
+                    j += 1;
                 }
+                j += 1;
             }
+            return Some((j + 1).min(n));
         }
-
-        return block;
+        if c == '-' && i + 1 < n && chars[i + 1] == '-' {
+            if let Some((level, body)) = Self::long_bracket_open(chars, i + 2) {
+                return Some(Self::long_bracket_close(chars, body, level).map_or(n, |(_, after)| after));
+            }
+            return Some(chars[i..].iter().position(|&c| c == '\n').map_or(n, |p| i + p));
+        }
+        Self::long_bracket_open(chars, i)
+            .map(|(level, body)| Self::long_bracket_close(chars, body, level).map_or(n, |(_, after)| after))
+    }
+
+    /// 在表构造器内容中，从 start 开始找到当前项的结束位置（顶层的 `,` / `;` 或内容末尾）
+    /// 需要跳过字符串、注释、嵌套括号以及 function ... end 等语句块
+    fn table_entry_end(chars: &[char], start: usize) -> usize {
+        let n = chars.len();
+        let mut i = start;
+        let mut bracket_depth = 0i32;
+        let mut block_depth = 0i32;
+        while i < n {
+            if let Some(next) = Self::skip_string_or_comment(chars, i) {
+                i = next;
+                continue;
+            }
+            let c = chars[i];
+            if c.is_alphabetic() || c == '_' {
+                let word_start = i;
+                while i < n && (chars[i].is_alphanumeric() || chars[i] == '_') {
+                    i += 1;
+                }
+                let word: String = chars[word_start..i].iter().collect();
+                match word.as_str() {
+                    "function" | "do" | "if" | "repeat" => block_depth += 1,
+                    "end" | "until" => block_depth -= 1,
+                    _ => {}
+                }
+                continue;
+            }
+            match c {
+                '(' | '[' | '{' => bracket_depth += 1,
+                ')' | ']' | '}' => bracket_depth -= 1,
+                ',' | ';' if bracket_depth == 0 && block_depth <= 0 => return i,
+                _ => {}
+            }
+            i += 1;
+        }
+        n
+    }
+
+    /// 把表构造器的内容（不含外层花括号）切分为若干项，并把每项上方的文档注释与之关联
+    fn scan_table_entries(body: &str) -> Vec<TableEntry> {
+        let chars: Vec<char> = body.chars().collect();
+        let n = chars.len();
+        // 每个字符所在的行偏移
+        let mut line_of = Vec::with_capacity(n);
+        let mut line = 0;
+        for &c in &chars {
+            line_of.push(line);
+            if c == '\n' {
+                line += 1;
+            }
+        }
+        let mut entries = Vec::new();
+        let mut comments = Vec::<String>::new();
+        let mut doc_line = 0;
+        let mut i = 0usize;
+        while i < n {
+            let c = chars[i];
+            if c.is_whitespace() || c == ',' || c == ';' {
+                i += 1;
+                continue;
+            }
+            if c == '-' && i + 1 < n && chars[i + 1] == '-' {
+                if comments.is_empty() {
+                    doc_line = line_of[i];
+                }
+                if let Some((level, start)) = Self::long_bracket_open(&chars, i + 2) {
+                    // --[[@brief ...]] 形式的块注释，内容以 @ 开头才视为文档
+                    let (end, after) = Self::long_bracket_close(&chars, start, level).unwrap_or((n, n));
+                    let text: String = chars[start..end].iter().collect();
+                    if text.trim_start().starts_with('@') || !comments.is_empty() {
+                        for l in text.lines().filter(|l| !is_space_line(l)) {
+                            comments.push(l.trim().to_string());
+                        }
+                    }
+                    i = after;
+                } else {
+                    let end = chars[i..].iter().position(|&c| c == '\n').map_or(n, |p| i + p);
+                    let line: String = chars[i..end].iter().collect();
+                    if Self::is_doc_comment(&line) || !comments.is_empty() {
+                        comments.push(line);
+                    }
+                    i = end;
+                }
+                continue;
+            }
+            let end = Self::table_entry_end(&chars, i);
+            let source: String = chars[i..end].iter().collect();
+            entries.push(TableEntry {
+                comments: std::mem::take(&mut comments),
+                source: source.trim().to_string(),
+                doc_line,
+                line: line_of[i],
+            });
+            i = end;
+        }
+        entries
+    }
+
+    /// 若 text 以 `{` 开头且在其中找到匹配的 `}`，返回花括号内部的内容
+    fn table_literal_body(text: &str) -> Option<String> {
+        let chars: Vec<char> = text.chars().collect();
+        if chars.first() != Some(&'{') {
+            return None;
+        }
+        let inner = &chars[1..];
+        Self::find_closing_brace(inner).map(|close| inner[..close].iter().collect())
+    }
+
+    /// 在 chars 中找到使花括号深度回到 -1 的位置（即外层表的闭合 `}`）
+    fn find_closing_brace(chars: &[char]) -> Option<usize> {
+        let n = chars.len();
+        let mut i = 0usize;
+        let mut depth = 0i32;
+        while i < n {
+            if let Some(next) = Self::skip_string_or_comment(chars, i) {
+                i = next;
+                continue;
+            }
+            let c = chars[i];
+            match c {
+                '{' => depth += 1,
+                '}' => {
+                    if depth == 0 {
+                        return Some(i);
+                    }
+                    depth -= 1;
+                }
+                _ => {}
+            }
+            i += 1;
+        }
+        None
+    }
+
+    /// 解析表构造器 `owner = { ... }` 中带文档注释的项
+    /// 函数值生成 `function owner.key(args)` 形式的签名，数据字段生成 `owner.key = value`；
+    /// first_line 是 body 第一行（即 `{` 所在行）在源文件中的行号
+    pub fn parse_table_literal(owner: &str, body: &str, first_line: usize) -> Vec<DocBlock> {
+        let mut blocks = Vec::new();
+        for entry in Self::scan_table_entries(body) {
+            if entry.comments.is_empty() {
+                continue;
+            }
+            let caps = match TABLE_KEY_RE.captures(&entry.source) {
+                Some(caps) => caps,
+                None => continue,
+            };
+            let key = &caps[1];
+            let value = caps[2].trim();
+
+            let mut block = LuaFileParser::create_docblock(entry.comments, InputFileType::Lua);
+            block.owner_object = owner.to_string();
+            block.start_line = first_line + entry.doc_line;
+            block.end_line = first_line + entry.line;
+            if let Some(rest) = value.strip_prefix("function") {
+                let params = match (rest.find('('), rest.find(')')) {
+                    (Some(l), Some(r)) if l < r => &rest[l..=r],
+                    _ => "()",
+                };
+                block.signature = Signature::parse_lua(&format!("function {}.{}{}", owner, key, params));
+                block.is_member = block.signature.is_member();
+                LuaFileParser::cross_reference_params(&mut block);
+            } else {
+                let value_line = value.lines().next().unwrap_or("").trim_end();
+                block.signature = Signature::parse_lua(&format!("{}.{} = {}", owner, key, value_line));
+            }
+            blocks.push(block);
+        }
+        blocks
+    }
+
+    /// 截断单行函数的函数体，只保留到参数列表的 `)` 为止
+    /// 例如 `function f() return 1 end` -> `function f()`
+    pub fn truncate_signature(code_line: &str) -> String {
+        let open = match code_line.find('(') {
+            Some(idx) => idx,
+            None => return code_line.trim_end().to_string(),
+        };
+        match code_line[open..].find(')') {
+            Some(close) => code_line[..open + close + 1].to_string(),
+            None => code_line.trim_end().to_string(),
+        }
+    }
+
+    /// 用文档行和完整的函数声明创建一个函数的 DocBlock
+    fn create_function_block(doc_lines: Vec<String>, code_line: &str) -> DocBlock {
+        let mut block = LuaFileParser::create_docblock(doc_lines, InputFileType::Lua);
+        block.signature = Signature::parse_lua(&LuaFileParser::truncate_signature(code_line));
+        if block.signature.keyword == "local function" {
+            block.is_local = true;
+            block.is_member = false;
+            block.owner_object = "".to_string();
+        } else {
+            block.owner_object = block.signature.owner.clone();
+            block.is_member = block.signature.is_member();
+        }
+        LuaFileParser::attach_receiver(&mut block);
+        LuaFileParser::cross_reference_params(&mut block);
+        block
+    }
+
+    /// 为 `function Account:deposit(amount)` 这样的方法记录隐含的 self 参数
+    /// 写了 `@param self` 时取出该项作为接收者（类型未写时仍用所属对象），否则类型即所属对象
+    fn attach_receiver(block: &mut DocBlock) {
+        if !block.signature.is_method {
+            return;
+        }
+        let mut receiver = match block.parameters.iter().position(|p| p.name == "self") {
+            Some(pos) => block.parameters.remove(pos),
+            None => Parameter::new("self", "", "", 0),
+        };
+        if receiver.type_name.is_empty() {
+            receiver.type_name = block.signature.owner.clone();
+        }
+        Parameter::renumber(&mut block.parameters);
+        block.receiver = Some(receiver);
+    }
+
+    /// 按签名中的参数列表对照 @param：参数按签名顺序排列，未写 @param 的参数也会列出
+    /// 写了 @param 却与签名不一致时记录警告；完全没有 @param 的函数只补全参数，不警告
+    fn cross_reference_params(block: &mut DocBlock) {
+        let documented = !block.parameters.is_empty();
+        let notes = block.sort_params_by_signature();
+        if documented {
+            for note in notes {
+                block.warn("param-mismatch", note);
+            }
+        }
+    }
+
+    /// 用文档行和表定义 `local Config = {` 创建表本身的 DocBlock（@field 等描述表的结构）
+    fn create_table_block(doc_lines: Vec<String>, definition: &str) -> DocBlock {
+        let mut block = LuaFileParser::create_docblock(doc_lines, InputFileType::Lua);
+        block.signature = Signature::parse_lua(&format!("{} {{}}", definition.trim()));
+        block.owner_object = block.signature.owner.clone();
+        block
+    }
+
+    /// 注释内容的缩进宽度：注释标记之后（块注释内部的行没有标记）的空白列数，制表符按 4 列计
+    fn comment_indent(line: &str) -> usize {
+        let rest = match line.trim_start().strip_prefix("--") {
+            Some(r) => r.strip_prefix('-').unwrap_or(r),
+            None => line,
+        };
+        rest.chars()
+            .take_while(|c| c.is_whitespace())
+            .map(|c| if c == '\t' { 4 } else { 1 })
+            .sum()
+    }
+
+    /// 把子标签内容放到所属标签下：@description / @note 下是描述项，@param 下附加到最近的参数
+    fn attach_description(block: &mut DocBlock, tag: &str, desc: Description) {
+        match tag {
+            "description" | "note" => block.descriptions.push(desc),
+            "param" => {
+                if let Some(p) = block.parameters.last_mut() {
+                    p.details.push(desc);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// 解析并创建一个 DocBlock
+    /// 这里采用了两层解析结构：
+    /// 1. 第一层：识别 @tag
+    /// 2. 第二层：如果处于 @description 下，识别 \subtag
+    ///
+    /// lang 为源文件的语言，没有指定语言的 `\code` 代码块使用该语言
+    pub fn create_docblock(buf: Vec<String>, lang: InputFileType) -> DocBlock {
+        for str in &buf {
+            log::debug!("Doc Line: {}", str);
+        }
+        let mut block = DocBlock::new(lang);
+
+        // 简单的状态机，用于处理多行内容（例如 description 下的子标签）
+        let mut current_tag = String::new();
+        // 正在读取的 \list 列表：(第一项的缩进, 每级缩进的宽度)
+        let mut list: Option<(Option<usize>, Option<usize>)> = None;
+        // @example 示例代码第一行的缩进
+        let mut example_indent: Option<usize> = None;
+
+        for line in buf {
+            // 1. 清理注释符号，获取纯文本内容
+            // 块注释内部的行没有注释标记，直接使用去掉缩进后的内容；
+            // 只有位于内容开头的 @ 与 \ 才是标签，正文中间的（如邮箱地址）按普通文字处理
+            let content = LuaFileParser::strip_lua_comment_leader(&line).unwrap_or(line.trim());
+
+            // @example 之后、下一个标签之前的行是示例代码：保留相对缩进，不解析其中的 @ 与 \
+            if current_tag == "example" && !content.starts_with('@') {
+                let indent = LuaFileParser::comment_indent(&line);
+                let base = *example_indent.get_or_insert(indent);
+                let code = block.example.get_or_insert_with(String::new);
+                if !code.is_empty() {
+                    code.push('\n');
+                }
+                code.push_str(&" ".repeat(indent.saturating_sub(base)));
+                code.push_str(content);
+                continue;
+            }
+
+            // 2. 解析主标签 @xxx
+            if let Some(tag_line) = content.strip_prefix('@') {
+                let (tag, tag_arg, body) = split_tag(tag_line);
+                
+                current_tag = tag.to_string();
+                list = None;
+
+                match tag {
+                    "brief" => block.brief = body.to_string(),
+                    "param" => {
+                        if let Some(mut p) = parse_parameter(body, block.parameters.len()) {
+                            // Doxygen 风格的参数方向 @param[in] / @param[out] / @param[in,out]
+                            p.direction = tag_arg.map(|d| d.replace(char::is_whitespace, "").to_lowercase());
+                            block.parameters.push(p);
+                        }
+                    }
+                    "property" => {
+                        if let Some(p) = parse_parameter(body, block.properties.len()) {
+                            block.properties.push(p);
+                        }
+                    }
+                    "field" => {
+                        // EmmyLua `@field name type desc`，与 @param 格式相同
+                        if let Some(p) = parse_parameter(body, block.fields.len()) {
+                            block.fields.push(p);
+                        }
+                    }
+                    "return" => {
+                         let (type_name, description) = split_type(body);
+                         if type_name == "..." {
+                            // `@return ... desc`：可变数量的返回值，... 不是类型名
+                            block.ret_value.push(Parameter::new("...", "", description, block.ret_value.len()));
+                         } else if !type_name.is_empty() {
+                            block.ret_value.push(Parameter::new("", type_name, description, block.ret_value.len()));
+                         }
+                    }
+                    "yields" => {
+                        // `@yields type desc`：与 @return 相同，没有名字
+                        let (type_name, description) = split_type(body);
+                        if !type_name.is_empty() {
+                            block.yields.push(Parameter::new("", type_name, description, block.yields.len()));
+                        }
+                    }
+                    // `@throws Type desc` 注明错误类型；Lua 的 error("msg") 没有类型，`@error desc` 只有描述
+                    "throws" | "error" => {
+                        if body.is_empty() {
+                            block.warn("empty-tag", format!("@{} 缺少内容", tag));
+                        } else {
+                            let (type_name, description) = match tag {
+                                "throws" => body.split_once(char::is_whitespace).unwrap_or((body, "")),
+                                _ => ("", body),
+                            };
+                            block.errors.push(Parameter::new("", strip_backticks(type_name), description.trim(), block.errors.len()));
+                        }
+                    }
+                    "includes" => {
+                        // 简单逗号分隔
+                        for inc in body.split(',') {
+                            block.includes.push(inc.trim().to_string());
+                        }
+                    }
+                    "note" => {
+                        block.note = body.to_string();
+                        block.descriptions.push(Description {
+                            dtype: DescriptionType::Note(body.to_string()),
+                            content: body.to_string(),
+                        });
+                    }
+                    "since" => block.since = Some(body.to_string()),
+                    "complexity" => block.complexity = Some(body.to_string()),
+                    "author" => {
+                        if body.is_empty() {
+                            block.warn("empty-tag", format!("@{} 缺少内容", tag));
+                        } else {
+                            block.author = Some(match block.author.take() {
+                                Some(authors) => format!("{}, {}", authors, body),
+                                None => body.to_string(),
+                            });
+                        }
+                    }
+                    "version" => block.version = Some(body.to_string()),
+                    "async" => block.is_async = true,
+                    "example-file" => block.example_file = Some(body.to_string()),
+                    "example" => {
+                        // 同一行的内容是示例的第一行，之后的行由上面的示例模式收集
+                        example_indent = None;
+                        block.example = (!body.is_empty()).then(|| body.to_string());
+                    }
+                    "alias" => {
+                        if body.is_empty() {
+                            block.warn("empty-tag", format!("@{} 缺少内容", tag));
+                        } else {
+                            block.aliases.push(body.to_string());
+                        }
+                    }
+                    "group" => block.group = Some(body.to_string()),
+                    "deprecated" => block.deprecated = Some(body.to_string()),
+                    // EmmyLua `@class Name : Base`，只取类名
+                    "class" => {
+                        let name = body.split(':').next().unwrap_or("").trim();
+                        if !name.is_empty() {
+                            block.class = Some(name.to_string());
+                        }
+                    }
+                    "todo" => block.todos.push(body.to_string()),
+                    "see" => {
+                        if body.is_empty() {
+                            block.warn("empty-tag", format!("@{} 缺少内容", tag));
+                        } else {
+                            block.see.push(body.to_string());
+                        }
+                    }
+                    // EmmyLua `@generic T, K : Base`，可一次声明多个，约束写作 `K: Base`
+                    "generic" => {
+                        for decl in body.split(',').map(str::trim).filter(|d| !d.is_empty()) {
+                            let decl = match decl.split_once(':') {
+                                Some((name, bound)) => format!("{}: {}", name.trim(), bound.trim()),
+                                None => decl.to_string(),
+                            };
+                            block.generics.push(decl);
+                        }
+                    }
+                    "description" => {
+                        // 进入 description 模式，后续行可能包含 \text 等；同一行的文字作为第一段
+                        if !body.is_empty() {
+                            block.descriptions.push(Description {
+                                dtype: DescriptionType::Text(body.to_string()),
+                                content: body.to_string(),
+                            });
+                        }
+                    }
+                    _ => {
+                        block.warn("unknown-tag", format!("未知的标签: @{}", tag));
+                    }
+                }
+            } else if content.starts_with("\\") {
+                // 3. 解析子标签：@description（及穿插其中的 @note）下的子标签是描述项，
+                //    @param 下的子标签附加到最近的参数
+                let desc = match parse_subtag(content, block.lang) {
+                    Some(desc) => desc,
+                    None => continue,
+                };
+                // \list 之后的 `- item` 行是列表项，嵌套层级由相对第一项的缩进决定
+                list = matches!(desc.dtype, DescriptionType::BulletList(..)).then_some((None, None));
+                // 单独一行的 \list 只开始列表，本身不是列表项
+                if !(list.is_some() && desc.content.is_empty()) {
+                    LuaFileParser::attach_description(&mut block, &current_tag, desc);
+                }
+            } else if let (Some((base, unit)), Some(item)) = (list.as_mut(), content.strip_prefix("- ")) {
+                let indent = LuaFileParser::comment_indent(&line);
+                let depth = indent.saturating_sub(*base.get_or_insert(indent));
+                let level = if depth == 0 { 0 } else { depth.div_ceil(*unit.get_or_insert(depth)) };
+                let desc = Description {
+                    dtype: DescriptionType::BulletList(level as i32, item.to_string()),
+                    content: item.to_string(),
+                };
+                LuaFileParser::attach_description(&mut block, &current_tag, desc);
+            } else if content.is_empty() {
+                // 空注释行结束列表；在描述中是段落分隔，以内容为空的文字项表示
+                list = None;
+                let after_text = block.descriptions.last().is_some_and(|d| !d.content.is_empty());
+                if matches!(current_tag.as_str(), "description" | "note") && after_text {
+                    block.descriptions.push(Description { dtype: DescriptionType::Text(String::new()), content: String::new() });
+                }
+            } else if current_tag == "description" {
+                // @description 下不带子标签的行是普通文字
+                block.descriptions.push(Description {
+                    dtype: DescriptionType::Text(content.to_string()),
+                    content: content.to_string(),
+                });
+            } else if current_tag == "param" {
+                // @param 的续行：追加到最近参数的描述
+                if let Some(p) = block.parameters.last_mut() {
+                    if !p.description.is_empty() {
+                        p.description.push(' ');
+                    }
+                    p.description.push_str(content);
+                }
+            }
+        }
+
+        // 示例末尾的空注释行不属于代码，描述末尾的段落分隔也没有意义
+        if let Some(code) = block.example.as_mut() {
+            code.truncate(code.trim_end().len());
+        }
+        while block.descriptions.last().is_some_and(|d| d.content.is_empty()) {
+            block.descriptions.pop();
+        }
+        block
     }
 }
 impl FileParser for LuaFileParser {
-    fn parse(&self, file: &File) -> Vec<DocBlock> {
-        let reader = BufReader::new(file);
+    fn parse_reader(&self, reader: &mut dyn BufRead) -> Vec<DocBlock> {
         let mut line_buf = Vec::<String>::new();
         let mut doc_blocks = Vec::<DocBlock>::new();
         let mut real_code_line = String::new();
         let mut is_mutli_line_function_decl = false;
-        
-        for line in reader.lines() {
+        // 正在读取的表构造器: (表名, 从 `{` 开始累积的源码, `{` 所在的行号)
+        let mut table_literal: Option<(String, String, usize)> = None;
+        // 模块末尾 `return M` 导出的表名
+        let mut exported: Option<String> = None;
+        // 正在读取的块注释: (长括号等级, 已读到的注释内容)
+        let mut block_comment: Option<(usize, String)> = None;
+        // 最近一条分节横幅注释的标题，以及已经设置过分组的文档块数
+        let mut banner_group: Option<String> = None;
+        let mut grouped = 0;
+        // line_buf 中第一行文档所在的行号，以及最近一个块注释开始的行号（均从 1 开始）
+        let mut doc_start = 0;
+        let mut comment_start = 0;
+
+        for (idx, line) in reader.lines().enumerate() {
+            let line_no = idx + 1;
             match line {
                 Ok(l) => {
-                    println!("Read: {}", &l);
+                    log::debug!("Read: {}", &l);
+                    // 0. 处于表构造器中：累积源码直到花括号闭合，再整体解析其中的项
+                    if let Some((owner, text, first_line)) = table_literal.as_mut() {
+                        text.push('\n');
+                        text.push_str(&l);
+                        if let Some(body) = LuaFileParser::table_literal_body(text) {
+                            doc_blocks.extend(LuaFileParser::parse_table_literal(owner, &body, *first_line));
+                            table_literal = None;
+                        }
+                        continue;
+                    }
+
+                    // 块注释 `--[[ ... ]]`：读到结尾后，内容以 @ 开头（或紧跟在其他文档行之后）
+                    // 才作为文档行收集，否则整体跳过（例如被注释掉的代码）
+                    let comment = match block_comment.take() {
+                        Some((level, mut text)) => {
+                            let (line_text, closed) =
+                                LuaFileParser::block_comment_line(&l.chars().collect::<Vec<_>>(), level);
+                            text.push('\n');
+                            text.push_str(&line_text);
+                            Some((level, text, closed))
+                        }
+                        None => {
+                            comment_start = line_no;
+                            LuaFileParser::block_comment_open(&l)
+                        }
+                    };
+                    if let Some((level, text, closed)) = comment {
+                        if !closed {
+                            block_comment = Some((level, text));
+                        } else if text.trim_start().starts_with('@') || !line_buf.is_empty() {
+                            if line_buf.is_empty() {
+                                doc_start = comment_start;
+                            }
+                            // 保留行首缩进，\list 的嵌套层级由缩进决定
+                            line_buf.extend(text.lines().filter(|t| !is_space_line(t)).map(|t| t.trim_end().to_string()));
+                        }
+                        continue;
+                    }
+
+                    // 分节横幅：此前的文档块归入上一节，之后的函数归入新的一节
+                    if let Some(label) = LuaFileParser::banner_label(&l) {
+                        LuaFileParser::apply_banner_group(&mut doc_blocks[grouped..], &banner_group);
+                        grouped = doc_blocks.len();
+                        banner_group = Some(label);
+                        line_buf.clear();
+                        continue;
+                    }
+
                     // 1. 收集文档行：只要是符合文档标记的行，或者在收集过程中遇到的普通注释行
-                    let is_comment = l.trim_start().starts_with("--");
+                    //    文档块以 `---` 开头时，夹在其中的 `--` 注释是普通的说明性注释，直接跳过，
+                    //    不会并入上一个标签的内容；文档块本身用 `--` 时无法区分，仍作为续行处理。
+                    //    空的注释行（只有 `--`）总是保留，在描述中作为段落分隔
+                    let is_comment = LuaFileParser::is_annotation_line(&l);
+                    if !line_buf.is_empty()
+                        && is_comment
+                        && !LuaFileParser::is_doc_comment(&l)
+                        && LuaFileParser::is_triple_dash(&line_buf[0])
+                        && !LuaFileParser::is_triple_dash(&l)
+                        && !LuaFileParser::strip_lua_comment_leader(&l).is_some_and(str::is_empty)
+                    {
+                        continue;
+                    }
                     if LuaFileParser::is_doc_comment(&l) || (!line_buf.is_empty() && is_comment) {
+                         if line_buf.is_empty() {
+                             doc_start = line_no;
+                         }
                          line_buf.push(l);
                          continue;
                     }
@@ -488,15 +1544,38 @@ impl FileParser for LuaFileParser {
                         continue;
                     }
 
+                    // 表构造器 `local M = { ... }`：进入表内部解析各项的文档
+                    if !is_mutli_line_function_decl {
+                        if let Some(caps) = TABLE_LITERAL_RE.captures(&l) {
+                            let owner = caps[1].to_string();
+                            let open = caps.get(0).unwrap().end() - 1;
+                            let text = l[open..].to_string();
+                            // 紧贴表定义的文档描述表本身
+                            if !line_buf.is_empty() {
+                                let mut block = LuaFileParser::create_table_block(line_buf.clone(), &l[..open]);
+                                (block.start_line, block.end_line) = (doc_start, line_no);
+                                doc_blocks.push(block);
+                            }
+                            line_buf.clear();
+                            match LuaFileParser::table_literal_body(&text) {
+                                Some(body) => doc_blocks.extend(LuaFileParser::parse_table_literal(&owner, &body, line_no)),
+                                None => table_literal = Some((owner, text, line_no)),
+                            }
+                            continue;
+                        }
+                    }
+
                     // 2. 解析代码行
                     let code_content = LuaFileParser::remove_annotation(&l);
                     
                     // 简单判断是否开始函数定义
                     if code_content.trim_start().starts_with("function")
                         || code_content.trim_start().starts_with("local function")
+                        || LuaFileParser::is_anonymous_module_function(&code_content)
                     {
                         // 拼接多行函数声明
-                        if code_content.find("(").is_some() && !code_content.ends_with(")") {
+                        // 只有参数列表尚未闭合时才是多行声明；`function f() return 1 end` 是完整的单行函数
+                        if code_content.contains('(') && !code_content.contains(')') {
                              is_mutli_line_function_decl = true;
                              real_code_line += &code_content;
                         } else if LuaFileParser::is_api_tail(&code_content) || code_content.contains(")") {
@@ -505,18 +1584,8 @@ impl FileParser for LuaFileParser {
                              
                              // 核心逻辑：如果缓冲区有文档内容，则创建一个 Block 并关联
                              if !line_buf.is_empty() {
-                                 let mut block = LuaFileParser::create_docblock(line_buf.clone());
-                                 block.signature = real_code_line.clone();
-                                 let _m_ret = LuaFileParser::extract_owner_object(&real_code_line);
-                                 if _m_ret == "local" {
-                                    block.is_local = true;
-                                    block.is_member = false;
-                                    block.owner_object = "".to_string();
-                                 }else{
-                                    block.owner_object = _m_ret;
-                                    block.is_member = LuaFileParser::is_member_function(&real_code_line, &block.owner_object);
-                                 }
-                                 
+                                 let mut block = LuaFileParser::create_function_block(line_buf.clone(), &real_code_line);
+                                 (block.start_line, block.end_line) = (doc_start, line_no);
                                  doc_blocks.push(block);
                                  line_buf.clear(); // 消费掉 buffer
                              }
@@ -531,18 +1600,9 @@ impl FileParser for LuaFileParser {
                             is_mutli_line_function_decl = false;
                             
                             if !line_buf.is_empty() {
-                                let mut block = LuaFileParser::create_docblock(line_buf.clone());
-                                block.signature = real_code_line.clone();
-                                let _m_ret = LuaFileParser::extract_owner_object(&real_code_line);
-                                if _m_ret == "local" {
-                                   block.is_local = true;
-                                   block.is_member = false;
-                                   block.owner_object = "".to_string();
-                                } else {
-                                   block.owner_object = _m_ret;
-                                   // 现在 real_code_line 应该是完整的，包含 ')'，所以 is_member_function 不会 panic
-                                   block.is_member = LuaFileParser::is_member_function(&real_code_line, &block.owner_object);
-                                }
+                                // 现在 real_code_line 应该是完整的，包含 ')'，所以 is_member_function 不会 panic
+                                let mut block = LuaFileParser::create_function_block(line_buf.clone(), &real_code_line);
+                                (block.start_line, block.end_line) = (doc_start, line_no);
                                 doc_blocks.push(block);
                                 line_buf.clear();
                             }
@@ -551,33 +1611,1986 @@ impl FileParser for LuaFileParser {
                         // 如果还没结束，保持 is_mutli_line_function_decl = true，继续读下一行拼接
                         
                     } else {
+                        let top_level = !l.starts_with(char::is_whitespace);
+                        if let Some(name) = LuaFileParser::module_return(&code_content).filter(|_| top_level) {
+                            exported = Some(name);
+                        }
                         // 其他非空行代码，清空之前的 doc buffer (因为它没有紧跟函数)
                         // line_buf.clear(); 
                         // *注*: 这里看需求，如果允许 doc 上方有少量非空行干扰，可以不 clear
                         // 但通常 doc 紧贴 function。
                         line_buf.clear(); 
                     }
-                    
+                    
+                }
+                Err(_) => continue,
+            }
+        }
+
+        LuaFileParser::apply_banner_group(&mut doc_blocks[grouped..], &banner_group);
+        if let Some(table) = exported {
+            LuaFileParser::mark_exported(&mut doc_blocks, &table);
+        }
+        doc_blocks
+    }
+}
+
+/// 匹配 C 结构体/联合体/枚举定义的开头: `struct Name {` / `typedef struct Name {` / `enum {`
+static C_AGGREGATE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\s*(?:typedef\s+)?(struct|union|enum)\s*([A-Za-z_]\w*)?\s*\{").unwrap()
+});
+
+/// 匹配字段声明 `const char *name[4]` / `unsigned flag : 1`，分出类型、名字与数组后缀
+static C_FIELD_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(.*?)\b([A-Za-z_]\w*)\s*((?:\[[^\]]*\]\s*)*)(?::\s*\w+)?$").unwrap()
+});
+
+/// 匹配函数指针字段 `void (*callback)(int)` 中的名字
+static C_FN_POINTER_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"\(\s*\*\s*([A-Za-z_]\w*)\s*\)").unwrap());
+
+/// 匹配一个完整的 C 函数声明 `ret name(params)`（已去掉函数体与 `;`）
+static C_FUNCTION_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)^(.*?)\b([A-Za-z_]\w*)\s*\((.*)\)\s*$").unwrap()
+});
+
+/// 正在读取的结构体/枚举定义
+struct CAggregate {
+    keyword: &'static str,
+    name   : String,
+    doc    : Vec<String>,
+    fields : Vec<Parameter>,
+    /// 花括号嵌套深度，字段只在第一层收集
+    depth  : usize,
+}
+
+/// C 文件解析器
+///
+/// 识别 `///` 与 `/** */` 文档注释之后的：
+/// - 函数声明或定义（可跨多行，到 `{` 或 `;` 为止）
+/// - 结构体、联合体与枚举，以及其中每个字段的文档：
+///   字段上方的 `///` / `/** */` 注释，或字段后的 `///<` / `/**< */` 注释
+pub struct CFileParser;
+
+impl CFileParser {
+    /// 去掉 C 注释标记，返回注释内容：`/// x`、`/** x */`、块注释内部的 ` * x` -> `x`
+    fn comment_text(line: &str) -> String {
+        let t = line.trim();
+        let t = t.strip_suffix("*/").unwrap_or(t).trim_end();
+        let t = t
+            .strip_prefix("/**<")
+            .or_else(|| t.strip_prefix("///<"))
+            .or_else(|| t.strip_prefix("/**"))
+            .or_else(|| t.strip_prefix("/*!"))
+            .or_else(|| t.strip_prefix("///"))
+            .or_else(|| t.strip_prefix("//!"))
+            .or_else(|| t.strip_prefix('*'))
+            .unwrap_or(t);
+        t.trim().to_string()
+    }
+
+    /// 是否是文档注释的开头（`///`、`/**`，不含字段后的 `///<` / `/**<`）
+    fn is_doc_comment_start(line: &str) -> bool {
+        let t = line.trim_start();
+        (t.starts_with("///") && !t.starts_with("///<")) || (t.starts_with("/**") && !t.starts_with("/**<"))
+    }
+
+    /// 拆出行尾的 `///<` / `/**< */` 字段注释，返回 (代码部分, 注释内容)
+    fn split_trailing_doc(line: &str) -> (&str, Option<String>) {
+        match line.find("///<").or_else(|| line.find("/**<")) {
+            Some(idx) => (&line[..idx], Some(Self::comment_text(&line[idx..]))),
+            None => (line, None),
+        }
+    }
+
+    /// 没有 @brief 时，把标签之前的第一行文字作为 @brief（JavaDoc 风格的自动简介）
+    fn with_auto_brief(mut doc: Vec<String>) -> Vec<String> {
+        if !doc.iter().any(|l| l.starts_with("@brief")) {
+            if let Some(first) = doc.iter_mut().find(|l| !l.is_empty()).filter(|l| !l.starts_with('@')) {
+                *first = format!("@brief {}", first);
+            }
+        }
+        doc
+    }
+
+    /// 字段上方注释的纯文本，`@brief` 标记会被去掉
+    fn field_description(doc: &[String]) -> String {
+        doc.iter()
+            .map(|l| l.strip_prefix("@brief").unwrap_or(l).trim())
+            .filter(|l| !l.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// 解析一条结构体字段声明（不含 `;`），`int x, y` 会得到两个字段
+    fn parse_struct_fields(decl: &str) -> Vec<(String, String)> {
+        let decl = decl.trim();
+        if decl.is_empty() {
+            return vec![];
+        }
+        if let Some(caps) = C_FN_POINTER_RE.captures(decl) {
+            return vec![(caps[1].to_string(), decl.to_string())];
+        }
+        let mut fields = Vec::new();
+        let mut base_type = String::new();
+        for (i, part) in decl.split(',').enumerate() {
+            let caps = match C_FIELD_RE.captures(part.trim()) {
+                Some(caps) => caps,
+                None => continue,
+            };
+            let pointer_and_type = caps[1].trim();
+            if i == 0 {
+                base_type = pointer_and_type.trim_end_matches(['*', ' ']).to_string();
+            }
+            let type_name = if i == 0 { pointer_and_type.to_string() } else { format!("{} {}", base_type, pointer_and_type) };
+            let array = caps[3].replace(char::is_whitespace, "");
+            fields.push((caps[2].to_string(), format!("{}{}", type_name.trim(), array)));
+        }
+        fields
+    }
+
+    /// 解析函数参数列表（不含括号），返回 (名字, 类型)；`void` 与空列表没有参数，`...` 的名字为 `...`
+    pub fn parse_params(list: &str) -> Vec<(String, String)> {
+        let list = list.trim();
+        if list.is_empty() || list == "void" {
+            return vec![];
+        }
+        list.split(',')
+            .map(str::trim)
+            .map(|param| {
+                if param == "..." {
+                    return ("...".to_string(), String::new());
+                }
+                match Self::parse_struct_fields(param).into_iter().next() {
+                    Some((name, ty)) if !ty.is_empty() => (name, ty),
+                    // 只写了类型的参数 `int`，没有名字
+                    _ => (String::new(), param.to_string()),
+                }
+            })
+            .collect()
+    }
+
+    /// 用文档注释与完整的函数声明创建 DocBlock
+    /// Doxygen 的 `@param name desc` / `@return desc` 不写类型，类型取自函数声明
+    fn create_function_block(doc: Vec<String>, decl: &str) -> Option<DocBlock> {
+        let raw = decl.split_whitespace().collect::<Vec<_>>().join(" ");
+        let signature = Signature::parse_c(&raw)?;
+        let param_types: Vec<(String, String)> = C_FUNCTION_RE
+            .captures(&raw)
+            .map(|caps| Self::parse_params(&caps[3]))
+            .unwrap_or_default();
+
+        let mut block = LuaFileParser::create_docblock(Self::with_auto_brief(doc), InputFileType::C);
+        for p in block.parameters.iter_mut() {
+            p.description = format!("{} {}", p.type_name, p.description).trim().to_string();
+            p.type_name = param_types
+                .iter()
+                .find(|(name, _)| *name == p.name)
+                .map(|(_, ty)| ty.clone())
+                .unwrap_or_default();
+        }
+        for ret in block.ret_value.iter_mut() {
+            ret.description = format!("{} {}", ret.type_name, ret.description).trim().to_string();
+            ret.type_name = signature.ret.clone().unwrap_or_default();
+        }
+        block.is_local = signature.ret.as_deref().is_some_and(|r| r.split_whitespace().any(|w| w == "static"));
+        block.signature = signature;
+        Some(block)
+    }
+
+    /// 处理结构体/枚举体内第一层的一行代码
+    fn parse_aggregate_line(agg: &mut CAggregate, code: &str, description: String) {
+        let code = code.trim();
+        let decls: Vec<(String, String)> = if agg.keyword == "enum" {
+            code.split(',')
+                .filter_map(|item| {
+                    let name = item.split('=').next()?.trim();
+                    (!name.is_empty()).then(|| (name.to_string(), String::new()))
+                })
+                .collect()
+        } else {
+            code.split(';').flat_map(Self::parse_struct_fields).collect()
+        };
+        for (name, type_name) in decls {
+            agg.fields.push(Parameter::new(name, type_name, description.clone(), agg.fields.len()));
+        }
+    }
+
+    /// 读取结构体/枚举体内的一行（字段上方的文档注释在 doc 中，读取后清空），读到结尾的 `}` 时返回 true
+    fn read_aggregate_line(agg: &mut CAggregate, line: &str, doc: &mut Vec<String>) -> bool {
+        let (code, trailing) = Self::split_trailing_doc(line);
+        let opens = code.matches('{').count();
+        let closes = code.matches('}').count();
+        if agg.depth == 1 && closes == 0 {
+            let description = trailing.unwrap_or_else(|| Self::field_description(doc));
+            Self::parse_aggregate_line(agg, code, description);
+        }
+        doc.clear();
+        agg.depth = (agg.depth + opens).saturating_sub(closes);
+        if agg.depth > 0 {
+            return false;
+        }
+        // `typedef struct { ... } Name;`
+        if agg.name.is_empty() {
+            let tail = code[code.rfind('}').map_or(0, |i| i + 1)..].trim();
+            agg.name = tail.trim_end_matches(';').trim().to_string();
+        }
+        true
+    }
+
+    /// 结构体/枚举读取完毕，有文档时生成 DocBlock
+    fn finish_aggregate(agg: CAggregate, lang: InputFileType) -> Option<DocBlock> {
+        if agg.doc.is_empty() && agg.fields.iter().all(|f| f.description.is_empty()) {
+            return None;
+        }
+        let mut block = LuaFileParser::create_docblock(Self::with_auto_brief(agg.doc), lang);
+        block.signature = Signature {
+            raw: format!("{} {}", agg.keyword, agg.name).trim_end().to_string(),
+            keyword: agg.keyword.to_string(),
+            name: agg.name.clone(),
+            ..Default::default()
+        };
+        block.owner_object = agg.name;
+        block.fields = agg.fields;
+        Some(block)
+    }
+}
+
+impl FileParser for CFileParser {
+    fn parse_reader(&self, reader: &mut dyn BufRead) -> Vec<DocBlock> {
+        let mut blocks = Vec::new();
+        // 尚未被声明消费的文档注释内容
+        let mut doc = Vec::<String>::new();
+        let mut in_block_comment = false;
+        let mut aggregate: Option<CAggregate> = None;
+        // 跨多行的函数声明：(文档注释, 已读取的声明文本)
+        let mut function: Option<(Vec<String>, String)> = None;
+        let mut in_plain_comment = false;
+
+        for line in reader.lines().map_while(Result::ok) {
+            // 普通的 `/* ... */` 多行注释直接跳过，不影响前面的文档注释
+            if in_plain_comment {
+                in_plain_comment = !line.contains("*/");
+                continue;
+            }
+
+            // 1. 文档注释：`///` 行与 `/** ... */` 块
+            if in_block_comment {
+                doc.push(Self::comment_text(&line));
+                in_block_comment = !line.contains("*/");
+                continue;
+            }
+            if Self::is_doc_comment_start(&line) {
+                doc.push(Self::comment_text(&line));
+                in_block_comment = line.trim_start().starts_with("/**") && !line.contains("*/");
+                continue;
+            }
+            if line.trim_start().starts_with("/*") {
+                in_plain_comment = !line.contains("*/");
+                continue;
+            }
+
+            // 多行函数声明：读到 `{` 或 `;` 为止
+            if let Some((_, decl)) = function.as_mut() {
+                decl.push(' ');
+                decl.push_str(&line);
+                if let Some(end) = decl.find(['{', ';']) {
+                    let (doc, decl) = function.take().unwrap();
+                    blocks.extend(Self::create_function_block(doc, &decl[..end]));
+                }
+                continue;
+            }
+
+            // 2. 结构体/枚举体内部
+            if let Some(agg) = aggregate.as_mut() {
+                if Self::read_aggregate_line(agg, &line, &mut doc) {
+                    blocks.extend(Self::finish_aggregate(aggregate.take().unwrap(), InputFileType::C));
+                }
+                continue;
+            }
+
+            // 3. 结构体/枚举定义开头
+            if let Some(caps) = C_AGGREGATE_RE.captures(&line) {
+                let keyword = match &caps[1] {
+                    "struct" => "struct",
+                    "union" => "union",
+                    _ => "enum",
+                };
+                aggregate = Some(CAggregate {
+                    keyword,
+                    name: caps.get(2).map_or(String::new(), |m| m.as_str().to_string()),
+                    doc: std::mem::take(&mut doc),
+                    fields: vec![],
+                    depth: 1,
+                });
+                continue;
+            }
+
+            // 4. 文档注释后的函数声明，可能跨越多行（返回类型也可以单独占一行）
+            let code = line.trim();
+            if !doc.is_empty() && !code.is_empty() && !code.starts_with('#') {
+                match code.find(['{', ';']) {
+                    Some(end) => blocks.extend(Self::create_function_block(std::mem::take(&mut doc), &code[..end])),
+                    None => function = Some((std::mem::take(&mut doc), code.to_string())),
+                }
+                continue;
+            }
+
+            // 其他代码或空行使之前的文档注释失效
+            doc.clear();
+        }
+        blocks
+    }
+}
+
+/// 匹配 C++ 类定义的开头 `class Name`、`struct [[nodiscard]] Name final : public Base`，捕获关键字与名字
+static CPP_CLASS_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(class|struct|union)\s+(?:\[\[[^\]]*\]\]\s*)?([A-Za-z_]\w*)\b").unwrap()
+});
+
+/// 匹配 `namespace a::b`、`inline namespace v1` 与匿名的 `namespace`，捕获名字
+static CPP_NAMESPACE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(?:inline\s+)?namespace\b\s*([A-Za-z_][\w:]*)?\s*$").unwrap()
+});
+
+/// 匹配 `enum Name`、`enum class Name : int`，捕获名字
+static CPP_ENUM_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^enum\s+(?:(?:class|struct)\s+)?([A-Za-z_]\w*)?").unwrap()
+});
+
+/// 匹配访问控制标签 `public:` / `protected:` / `private:`，捕获级别
+static CPP_ACCESS_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^(public|protected|private)\s*:($|[^:])").unwrap());
+
+/// 声明前不影响签名含义的说明符，返回类型中去掉
+const CPP_SPECIFIERS: &[&str] = &[
+    "static", "virtual", "inline", "explicit", "constexpr", "consteval", "friend", "extern",
+];
+
+/// 正在读取的类（class/struct/union），结束时生成 DocBlock
+struct CppClass {
+    keyword  : &'static str,
+    /// 类声明的开头（含 `template<...>` 与基类），作为签名
+    signature: String,
+    doc      : Vec<String>,
+    /// 公开的数据成员
+    fields   : Vec<Parameter>,
+    /// 当前的访问级别是否为 private（class 默认 private，struct/union 默认 public）
+    private  : bool,
+    /// 类的 DocBlock 在结果中的位置，使其排在成员函数之前
+    index    : usize,
+}
+
+/// 当前所在的命名空间、类或 `extern "C"` 块
+struct CppScope {
+    /// 名字，匿名命名空间与 `extern "C"` 为空
+    name     : String,
+    /// 匿名命名空间：其中的声明只在本文件可见
+    anonymous: bool,
+    /// 作用域内部的花括号深度
+    depth    : usize,
+    class    : Option<CppClass>,
+}
+
+/// C++ 文件解析器
+///
+/// 注释的写法与 C 相同（`///`、`/** */`，字段后的 `///<`），在 C 的基础上识别：
+/// - `namespace` 与类（class/struct/union）的作用域：其中声明的 owner_object 为外层作用域的完整名字
+///   （`geo::Shape`），类体中的函数是成员函数；类有文档或公开的数据成员有文档时生成类的文档块
+/// - `ns::Class::method` 这样在类外定义的限定名，限定部分并入 owner_object
+/// - 声明前的 `template <...>` 前缀，模板参数作为 generics，`@tparam` 的说明附在对应的参数后
+/// - 构造与析构函数、运算符重载、尾置返回类型 `auto f() -> T` 与带默认值的（可选）参数
+/// - `enum` 与 `enum class`，枚举值的文档与 C 相同
+///
+/// 匿名命名空间中的声明、命名空间作用域的 `static` 函数以及类的 private 成员视为私有 (is_local)
+pub struct CppFileParser;
+
+impl CppFileParser {
+    /// 拆出开头的 `template <...>`，返回 (模板前缀, 其余部分)
+    fn strip_template(code: &str) -> (Option<&str>, &str) {
+        let rest = match code.strip_prefix("template") {
+            Some(rest) if rest.trim_start().starts_with('<') => rest,
+            _ => return (None, code),
+        };
+        let open = code.len() - rest.len() + rest.find('<').unwrap_or(0);
+        let mut depth = 0;
+        for (i, c) in code[open..].char_indices() {
+            match c {
+                '<' => depth += 1,
+                '>' => depth -= 1,
+                _ => {}
+            }
+            if depth == 0 {
+                let end = open + i + 1;
+                return (Some(&code[..end]), code[end..].trim_start());
+            }
+        }
+        (None, code)
+    }
+
+    /// 模板前缀中的参数：`typename T` -> `T`，`int N = 4` -> `N: int`，`std::integral T` -> `T: std::integral`
+    fn template_params(preamble: &str) -> Vec<String> {
+        let list = match (preamble.find('<'), preamble.rfind('>')) {
+            (Some(open), Some(close)) if open < close => &preamble[open + 1..close],
+            _ => return vec![],
+        };
+        Self::split_top_level(list)
+            .into_iter()
+            .filter_map(|param| {
+                let param = Self::strip_default(param).0;
+                let (kind, name) = param.rsplit_once(char::is_whitespace)?;
+                let name = name.trim_start_matches("...");
+                let kind = kind.trim().trim_end_matches("...").trim();
+                Some(match kind {
+                    "typename" | "class" => name.to_string(),
+                    _ => format!("{}: {}", name, kind),
+                })
+            })
+            .collect()
+    }
+
+    /// 取出文档中的 `@tparam T desc`，把说明附在对应的模板参数后；只在文档中出现的参数也加入
+    fn apply_tparams(doc: Vec<String>, generics: &mut Vec<String>) -> Vec<String> {
+        let mut lines = Vec::with_capacity(doc.len());
+        for line in doc {
+            let body = match line.strip_prefix("@tparam") {
+                Some(body) if body.is_empty() || body.starts_with(char::is_whitespace) => body.trim(),
+                _ => {
+                    lines.push(line);
+                    continue;
+                }
+            };
+            let (name, desc) = body.split_once(char::is_whitespace).unwrap_or((body, ""));
+            let desc = desc.trim();
+            match generics.iter_mut().find(|g| g.split(": ").next() == Some(name)) {
+                Some(_) if desc.is_empty() => {}
+                Some(g) if g.contains(": ") => *g = format!("{} — {}", g, desc),
+                Some(g) => *g = format!("{}: {}", g, desc),
+                None if desc.is_empty() => generics.push(name.to_string()),
+                None => generics.push(format!("{}: {}", name, desc)),
+            }
+        }
+        lines
+    }
+
+    /// 按不在括号（含尖括号）内的逗号切分
+    fn split_top_level(list: &str) -> Vec<&str> {
+        let mut parts = Vec::new();
+        let mut depth = 0i32;
+        let mut start = 0;
+        for (i, c) in list.char_indices() {
+            match c {
+                '(' | '[' | '{' | '<' => depth += 1,
+                ')' | ']' | '}' | '>' => depth -= 1,
+                ',' if depth == 0 => {
+                    parts.push(list[start..i].trim());
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        parts.push(list[start..].trim());
+        parts.retain(|p| !p.is_empty());
+        parts
+    }
+
+    /// 拆出参数或成员的默认值：`int x = 4` -> (`int x`, true)
+    fn strip_default(decl: &str) -> (&str, bool) {
+        let mut depth = 0i32;
+        for (i, c) in decl.char_indices() {
+            match c {
+                '(' | '[' | '{' | '<' => depth += 1,
+                ')' | ']' | '}' | '>' => depth -= 1,
+                '=' if depth == 0 && !decl[i + 1..].starts_with('=') => return (decl[..i].trim(), true),
+                _ => {}
+            }
+        }
+        (decl.trim(), false)
+    }
+
+    /// 解析函数参数列表（不含括号），返回 (名字, 类型, 是否有默认值)；只写了类型的参数名字为空
+    pub fn parse_params(list: &str) -> Vec<(String, String, bool)> {
+        if list.trim() == "void" {
+            return vec![];
+        }
+        Self::split_top_level(list)
+            .into_iter()
+            .map(|param| {
+                if param == "..." {
+                    return ("...".to_string(), String::new(), false);
+                }
+                let (decl, optional) = Self::strip_default(param);
+                match Self::parse_declarator(decl) {
+                    Some((name, ty)) => (name, ty, optional),
+                    None => (String::new(), decl.to_string(), optional),
+                }
+            })
+            .collect()
+    }
+
+    /// 解析一个声明 `const std::map<K, V> &m` / `int buf[4]`，返回 (名字, 类型)；只写了类型时返回 None
+    fn parse_declarator(decl: &str) -> Option<(String, String)> {
+        let decl = decl.trim();
+        if let Some(caps) = C_FN_POINTER_RE.captures(decl) {
+            return Some((caps[1].to_string(), decl.to_string()));
+        }
+        let caps = C_FIELD_RE.captures(decl)?;
+        let type_name = caps[1].trim();
+        if type_name.is_empty() || type_name.ends_with("::") {
+            return None;
+        }
+        Some((caps[2].to_string(), format!("{}{}", type_name, caps[3].replace(char::is_whitespace, ""))))
+    }
+
+    /// 拆开函数声明（已去掉模板前缀），返回 (参数表之前的部分, 参数表, 参数表之后的部分)
+    fn split_function(decl: &str) -> Option<(&str, &str, &str)> {
+        // `operator()` 的第一对括号属于名字
+        let search_from = match Self::find_operator(decl) {
+            Some(pos) => {
+                let after = pos + "operator".len();
+                let rest = decl[after..].trim_start();
+                decl.len() - rest.len() + if rest.starts_with("()") { 2 } else { 0 }
+            }
+            None => 0,
+        };
+        let mut angle = 0i32;
+        let mut open = None;
+        for (i, c) in decl[search_from..].char_indices() {
+            match c {
+                '<' if search_from == 0 => angle += 1,
+                '>' if search_from == 0 => angle -= 1,
+                '(' if angle <= 0 => {
+                    open = Some(search_from + i);
+                    break;
+                }
+                _ => {}
+            }
+        }
+        let open = open?;
+        let mut depth = 0;
+        for (i, c) in decl[open..].char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                _ => continue,
+            }
+            if depth == 0 {
+                let close = open + i;
+                let head = decl[..open].trim_end();
+                return (!head.is_empty()).then(|| (head, &decl[open + 1..close], &decl[close + 1..]));
+            }
+        }
+        None
+    }
+
+    /// `operator` 作为独立单词出现的位置
+    fn find_operator(text: &str) -> Option<usize> {
+        text.match_indices("operator").map(|(i, _)| i).find(|&i| {
+            let before = text[..i].chars().next_back();
+            let after = text[i + "operator".len()..].chars().next();
+            !before.is_some_and(|c| c.is_alphanumeric() || c == '_') && !after.is_some_and(|c| c.is_alphanumeric() || c == '_')
+        })
+    }
+
+    /// 把参数表之前的部分拆为 (返回类型与说明符, 可能带限定的名字)
+    fn split_name(head: &str) -> Option<(&str, &str)> {
+        let end = Self::find_operator(head).unwrap_or(head.len());
+        let mut start = end;
+        let mut angle = 0;
+        for (i, c) in head[..end].char_indices().rev() {
+            match c {
+                '>' => angle += 1,
+                '<' if angle > 0 => angle -= 1,
+                _ if angle > 0 => {}
+                c if c.is_alphanumeric() || c == '_' || c == ':' || c == '~' => {}
+                _ => break,
+            }
+            start = i;
+        }
+        let name = head[start..].trim_start_matches("::");
+        (!name.is_empty()).then(|| (head[..start].trim(), name))
+    }
+
+    /// 限定名中最后一个不在尖括号内的 `::` 的位置
+    fn rfind_scope(path: &str) -> Option<usize> {
+        // 运算符名字中的 `<`、`>` 不是尖括号
+        let path = &path[..Self::find_operator(path).unwrap_or(path.len())];
+        let mut angle = 0;
+        let mut found = None;
+        for (i, c) in path.char_indices() {
+            match c {
+                '<' => angle += 1,
+                '>' => angle -= 1,
+                ':' if angle == 0 && path[i..].starts_with("::") => found = Some(i),
+                _ => {}
+            }
+        }
+        found
+    }
+
+    /// 去掉 `static`、`virtual` 等说明符与 `[[nodiscard]]` 等属性
+    fn strip_specifiers(text: &str) -> String {
+        let mut rest = text;
+        let mut kept = Vec::new();
+        while let Some(start) = rest.find("[[") {
+            kept.push(&rest[..start]);
+            rest = rest[start..].find("]]").map_or("", |end| &rest[start + end + 2..]);
+        }
+        kept.push(rest);
+        kept.iter()
+            .flat_map(|part| part.split_whitespace())
+            .filter(|w| !CPP_SPECIFIERS.contains(w))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    /// 作用域的完整名字 `a::b::C`
+    fn scope_path(scopes: &[CppScope]) -> String {
+        scopes.iter().filter(|s| !s.name.is_empty()).map(|s| s.name.as_str()).collect::<Vec<_>>().join("::")
+    }
+
+    /// 用 `::` 连接非空的名字
+    fn join_path(owner: &str, name: &str) -> String {
+        match (owner.is_empty(), name.is_empty()) {
+            (true, _) => name.to_string(),
+            (_, true) => owner.to_string(),
+            _ => format!("{}::{}", owner, name),
+        }
+    }
+
+    /// 当前位置的声明是否私有：在匿名命名空间中，或在类的 private 部分
+    fn in_private_scope(scopes: &[CppScope]) -> bool {
+        scopes.iter().any(|s| s.anonymous) || scopes.last().is_some_and(|s| s.class.as_ref().is_some_and(|c| c.private))
+    }
+
+    /// 用文档注释与完整的函数声明创建 DocBlock；classes 为已知的类的完整名字
+    fn create_function_block(doc: Vec<String>, decl: &str, scopes: &[CppScope], classes: &[String]) -> Option<DocBlock> {
+        let raw = decl.split_whitespace().collect::<Vec<_>>().join(" ");
+        let mut signature = Signature::parse_cpp(&raw)?;
+        let (template, rest) = Self::strip_template(&raw);
+        let (head, params, _) = Self::split_function(rest)?;
+        let param_types = Self::parse_params(params);
+
+        let mut generics = template.map(Self::template_params).unwrap_or_default();
+        let doc = Self::apply_tparams(doc, &mut generics);
+        let mut block = LuaFileParser::create_docblock(CFileParser::with_auto_brief(doc), InputFileType::Cpp);
+        for p in block.parameters.iter_mut() {
+            p.description = format!("{} {}", p.type_name, p.description).trim().to_string();
+            let declared = param_types.iter().find(|(name, _, _)| *name == p.name);
+            p.type_name = declared.map(|(_, ty, _)| ty.clone()).unwrap_or_default();
+            p.optional |= declared.is_some_and(|(_, _, optional)| *optional);
+        }
+        for ret in block.ret_value.iter_mut() {
+            ret.description = format!("{} {}", ret.type_name, ret.description).trim().to_string();
+            ret.type_name = signature.ret.clone().unwrap_or_default();
+        }
+        block.generics.extend(generics);
+
+        let in_class = scopes.last().is_some_and(|s| s.class.is_some());
+        signature.owner = Self::join_path(&Self::scope_path(scopes), &signature.owner);
+        block.owner_object = signature.owner.clone();
+        block.is_member = in_class || classes.contains(&signature.owner);
+        let is_static = head.split_whitespace().any(|w| w == "static");
+        block.is_local = Self::in_private_scope(scopes) || (is_static && !block.is_member);
+        block.signature = signature;
+        Some(block)
+    }
+
+    /// 类读取完毕，有文档（或公开的数据成员有文档）时生成 DocBlock
+    fn finish_class(name: String, class: CppClass, owner: &str, private: bool) -> Option<DocBlock> {
+        if class.doc.is_empty() && class.fields.iter().all(|f| f.description.is_empty()) {
+            return None;
+        }
+        let (template, _) = Self::strip_template(&class.signature);
+        let mut generics = template.map(Self::template_params).unwrap_or_default();
+        let doc = Self::apply_tparams(class.doc, &mut generics);
+        let mut block = LuaFileParser::create_docblock(CFileParser::with_auto_brief(doc), InputFileType::Cpp);
+        block.generics.extend(generics);
+        block.signature = Signature {
+            raw: class.signature,
+            keyword: class.keyword.to_string(),
+            owner: owner.to_string(),
+            name: name.clone(),
+            ..Default::default()
+        };
+        block.owner_object = Self::join_path(owner, &name);
+        block.class = Some(block.owner_object.clone());
+        block.fields = class.fields;
+        block.is_local = private;
+        Some(block)
+    }
+
+    /// 枚举读取完毕，有文档时生成 DocBlock，签名使用声明原文（`enum class Color : uint8_t`）
+    fn finish_enum(agg: CAggregate, raw: String, owner: &str, private: bool) -> Option<DocBlock> {
+        let mut block = CFileParser::finish_aggregate(agg, InputFileType::Cpp)?;
+        block.signature.raw = raw;
+        block.signature.owner = owner.to_string();
+        block.owner_object = Self::join_path(owner, &block.signature.name);
+        block.is_local = private;
+        Some(block)
+    }
+
+    /// 类体中的数据成员声明（不含结尾的 `;`），公开的成员记入类的 fields
+    fn add_class_fields(class: &mut CppClass, decl: &str, description: String) {
+        if class.private {
+            return;
+        }
+        // `int x, y` 中后面的名字沿用第一个的基本类型
+        let mut base_type = String::new();
+        for (i, part) in Self::split_top_level(&Self::strip_specifiers(decl)).into_iter().enumerate() {
+            let part = Self::strip_default(part).0;
+            let declared = match i {
+                0 => Self::parse_declarator(part),
+                _ => Self::parse_declarator(&format!("{} {}", base_type, part)),
+            };
+            let (name, type_name) = match declared {
+                Some(declared) => declared,
+                None => continue,
+            };
+            if i == 0 {
+                base_type = type_name.split('[').next().unwrap_or("").trim_end_matches(['*', '&', ' ']).to_string();
+            }
+            class.fields.push(Parameter::new(name, type_name, description.clone(), class.fields.len()));
+        }
+    }
+
+    /// 是否是需要跟踪的声明的开头（命名空间、类、枚举、模板或 `extern "C"` 块），没有文档时也要读取
+    fn starts_scope(code: &str) -> bool {
+        let code = Self::strip_template(code).1;
+        code.starts_with("template")
+            || code.starts_with("extern \"C")
+            || CPP_NAMESPACE_RE.is_match(code.split('{').next().unwrap_or(code).trim())
+            || CPP_CLASS_RE.is_match(code)
+            || CPP_ENUM_RE.is_match(code)
+    }
+
+    /// 声明结束的位置：不在括号内的第一个 `{` 或 `;`
+    fn find_terminator(decl: &str) -> Option<usize> {
+        let mut depth = 0i32;
+        for (i, c) in decl.char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => depth -= 1,
+                '{' | ';' if depth <= 0 => return Some(i),
+                _ => {}
+            }
+        }
+        None
+    }
+}
+
+impl FileParser for CppFileParser {
+    fn parse_reader(&self, reader: &mut dyn BufRead) -> Vec<DocBlock> {
+        let mut blocks = Vec::new();
+        // 尚未被声明消费的文档注释内容
+        let mut doc = Vec::<String>::new();
+        let mut in_block_comment = false;
+        let mut in_plain_comment = false;
+        let mut scopes: Vec<CppScope> = Vec::new();
+        // 已知的类的完整名字，类外定义的 `Class::method` 据此判断为成员函数
+        let mut classes: Vec<String> = Vec::new();
+        // 当前的花括号深度
+        let mut depth = 0;
+        // 跨多行的声明：(文档注释, 已读取的声明文本)
+        let mut pending: Option<(Vec<String>, String)> = None;
+        // 正在读取的枚举：(定义, 签名, 所属作用域的完整名字, 是否私有)
+        let mut aggregate: Option<(CAggregate, String, String, bool)> = None;
+
+        for line in reader.lines().map_while(Result::ok) {
+            // 普通的 `/* ... */` 多行注释直接跳过，不影响前面的文档注释
+            if in_plain_comment {
+                in_plain_comment = !line.contains("*/");
+                continue;
+            }
+
+            // 1. 文档注释：`///` 行与 `/** ... */` 块
+            if in_block_comment {
+                doc.push(CFileParser::comment_text(&line));
+                in_block_comment = !line.contains("*/");
+                continue;
+            }
+            if CFileParser::is_doc_comment_start(&line) {
+                doc.push(CFileParser::comment_text(&line));
+                in_block_comment = line.trim_start().starts_with("/**") && !line.contains("*/");
+                continue;
+            }
+            if line.trim_start().starts_with("/*") {
+                in_plain_comment = !line.contains("*/");
+                continue;
+            }
+
+            // 2. 枚举体内部，与 C 相同
+            if let Some((agg, _, _, _)) = aggregate.as_mut() {
+                if CFileParser::read_aggregate_line(agg, &line, &mut doc) {
+                    let (agg, raw, owner, private) = aggregate.take().unwrap();
+                    blocks.extend(Self::finish_enum(agg, raw, &owner, private));
+                }
+                continue;
+            }
+
+            let (code, trailing) = CFileParser::split_trailing_doc(&line);
+            let code = code.find("//").map_or(code, |i| &code[..i]).trim();
+            let scope_depth = scopes.last().map_or(0, |s| s.depth);
+
+            // 3. 声明的开头：只在命名空间或类的作用域中（不在函数体内）读取
+            if pending.is_none() {
+                let at_scope_level = depth == scope_depth;
+                let class = scopes.last_mut().and_then(|s| s.class.as_mut()).filter(|_| at_scope_level);
+                if let Some((class, caps)) = class.zip(CPP_ACCESS_RE.captures(code)) {
+                    class.private = &caps[1] == "private";
+                    doc.clear();
+                    continue;
+                }
+                let declaration = !doc.is_empty() || trailing.is_some() || Self::starts_scope(code);
+                if at_scope_level && declaration && !code.is_empty() && !code.starts_with('#') {
+                    pending = Some((std::mem::take(&mut doc), String::new()));
+                } else {
+                    doc.clear();
+                }
+            }
+
+            // 4. 读到 `{` 或 `;` 时处理完整的声明（`template <...>` 单独一行时继续读取）
+            let depth_before = depth;
+            if let Some((_, decl)) = pending.as_mut() {
+                if !decl.is_empty() {
+                    decl.push(' ');
+                }
+                decl.push_str(code);
+                if let Some(end) = Self::find_terminator(decl) {
+                    let (decl_doc, decl) = pending.take().unwrap();
+                    let opens = decl.as_bytes()[end] == b'{';
+                    let head = decl[..end].trim();
+                    let (_, body) = Self::strip_template(head);
+                    let owner = Self::scope_path(&scopes);
+                    let private = Self::in_private_scope(&scopes);
+                    let raw = head.split_whitespace().collect::<Vec<_>>().join(" ");
+
+                    if let Some(caps) = CPP_NAMESPACE_RE.captures(body).filter(|_| opens) {
+                        let name = caps.get(1).map_or("", |m| m.as_str());
+                        let scope = CppScope { name: name.to_string(), anonymous: name.is_empty(), depth: depth_before + 1, class: None };
+                        scopes.push(scope);
+                    } else if body.starts_with("extern \"C") && opens {
+                        scopes.push(CppScope { name: String::new(), anonymous: false, depth: depth_before + 1, class: None });
+                    } else if let Some(caps) = CPP_ENUM_RE.captures(body).filter(|_| opens) {
+                        let mut agg = CAggregate {
+                            keyword: "enum",
+                            name: caps.get(1).map_or(String::new(), |m| m.as_str().to_string()),
+                            doc: decl_doc,
+                            fields: vec![],
+                            depth: 1,
+                        };
+                        // 与开头同一行的枚举值
+                        let rest = &decl[end + 1..];
+                        let description = trailing.unwrap_or_default();
+                        match rest.find('}') {
+                            Some(close) => {
+                                CFileParser::parse_aggregate_line(&mut agg, &rest[..close], description);
+                                blocks.extend(Self::finish_enum(agg, raw, &owner, private));
+                            }
+                            None => {
+                                CFileParser::parse_aggregate_line(&mut agg, rest, description);
+                                aggregate = Some((agg, raw, owner, private));
+                            }
+                        }
+                        continue;
+                    } else if let Some(caps) = CPP_CLASS_RE.captures(body).filter(|_| !body.contains(['=', '('])) {
+                        if opens {
+                            let keyword = match &caps[1] {
+                                "class" => "class",
+                                "struct" => "struct",
+                                _ => "union",
+                            };
+                            let name = caps[2].to_string();
+                            classes.push(Self::join_path(&owner, &name));
+                            let class = CppClass {
+                                keyword,
+                                signature: raw,
+                                doc: decl_doc,
+                                fields: vec![],
+                                private: keyword == "class",
+                                index: blocks.len(),
+                            };
+                            scopes.push(CppScope { name, anonymous: false, depth: depth_before + 1, class: Some(class) });
+                        }
+                    } else {
+                        let description = trailing.unwrap_or_else(|| CFileParser::field_description(&decl_doc));
+                        let function = match decl_doc.is_empty() || !body.contains('(') {
+                            true => None,
+                            false => Self::create_function_block(decl_doc, head, &scopes, &classes),
+                        };
+                        match (function, scopes.last_mut().and_then(|s| s.class.as_mut())) {
+                            (Some(block), _) => blocks.push(block),
+                            // 类体中的数据成员
+                            (None, Some(class)) => Self::add_class_fields(class, body, description),
+                            (None, None) => {}
+                        }
+                    }
+                }
+            }
+
+            // 5. 花括号深度；离开作用域时结束其中的类
+            depth = (depth + code.matches('{').count()).saturating_sub(code.matches('}').count());
+            while scopes.last().is_some_and(|s| s.depth > depth) {
+                let scope = scopes.pop().unwrap();
+                if let Some(class) = scope.class {
+                    let index = class.index;
+                    let owner = Self::scope_path(&scopes);
+                    if let Some(block) = Self::finish_class(scope.name, class, &owner, Self::in_private_scope(&scopes)) {
+                        blocks.insert(index, block);
+                    }
+                }
+            }
+        }
+        blocks
+    }
+}
+
+/// Rust 文件解析器
+///
+/// 收集 `fn`、`struct`、`enum` 声明上方连续的 `///` 文档注释，以及 `//!` 模块文档。
+/// 文档按 rustdoc 的 Markdown 约定解析：
+/// - 第一段作为 brief，其余段落与代码块作为描述
+/// - `# Arguments` 下的列表项作为参数，类型取自函数签名
+/// - `# Returns` 下的文字作为返回值说明，类型取自签名中的 `->`
+/// - `# Examples` 下的第一个代码块作为示例
+pub struct RustFileParser;
+
+/// 匹配 rustdoc `# Arguments` 下的列表项: `* `name` - desc` / `- name: desc`
+static RUST_ARG_ITEM_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^[*-]\s+`?([A-Za-z_]\w*)`?\s*(?:[-:–—]\s*)?(.*)$").unwrap()
+});
+
+impl RustFileParser {
+    /// 去掉可见性与 async/const/unsafe/extern 等修饰，`pub(crate) async fn f` -> `fn f`
+    fn strip_qualifiers(mut code: &str) -> &str {
+        loop {
+            let rest = ["pub(crate)", "pub(super)", "pub", "async", "const", "unsafe", "extern \"C\"", "extern"]
+                .iter()
+                .find_map(|q| code.strip_prefix(q).filter(|r| r.starts_with(char::is_whitespace)));
+            match rest {
+                Some(rest) => code = rest.trim_start(),
+                None => return code,
+            }
+        }
+    }
+
+    /// 代码行是否以 fn/struct/enum 声明开头
+    fn is_item_start(code: &str) -> bool {
+        let rest = Self::strip_qualifiers(code.trim());
+        ["fn", "struct", "enum"]
+            .iter()
+            .any(|kw| rest.strip_prefix(kw).is_some_and(|r| r.starts_with(char::is_whitespace)))
+    }
+
+    /// 文档注释行的内容：(是否为 `//!` 模块文档, 去掉标记与一个前导空格后的文字)
+    fn doc_text(line: &str) -> Option<(bool, &str)> {
+        let t = line.trim_start();
+        let (inner, rest) = match t.strip_prefix("//!") {
+            Some(rest) => (true, rest),
+            // `////` 是普通注释
+            None => (false, t.strip_prefix("///").filter(|r| !r.starts_with('/'))?),
+        };
+        Some((inner, rest.strip_prefix(' ').unwrap_or(rest).trim_end()))
+    }
+
+    /// 按顶层逗号切分，忽略 `<>`、`()`、`[]`、`{}` 内部的逗号（`->` 中的 `>` 不计入）
+    fn split_top_level(list: &str) -> Vec<&str> {
+        let mut parts = Vec::new();
+        let mut depth = 0i32;
+        let mut start = 0;
+        let mut prev = '\0';
+        for (i, c) in list.char_indices() {
+            match c {
+                '<' | '(' | '[' | '{' => depth += 1,
+                '>' if prev != '-' => depth -= 1,
+                ')' | ']' | '}' => depth -= 1,
+                ',' if depth == 0 => {
+                    parts.push(&list[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+            prev = c;
+        }
+        parts.push(&list[start..]);
+        parts.into_iter().map(str::trim).filter(|p| !p.is_empty()).collect()
+    }
+
+    /// 解析函数参数列表（不含括号），返回 (名字, 类型)；`self` 接收者不算参数
+    pub fn parse_params(list: &str) -> Vec<(String, String)> {
+        Self::split_top_level(list)
+            .into_iter()
+            .filter_map(|param| {
+                let (pattern, ty) = param.split_once(':')?;
+                let name = pattern.trim().trim_start_matches("mut ").trim();
+                (name != "self").then(|| (name.to_string(), ty.trim().to_string()))
+            })
+            .collect()
+    }
+
+    /// 把 rustdoc 文档写入 block；param_types 为签名中的 (参数名, 类型)
+    fn apply_docs(block: &mut DocBlock, doc: &[String], param_types: &[(String, String)]) {
+        let mut section = String::new();
+        let mut paragraph: Vec<&str> = Vec::new();
+        let mut code: Option<(InputFileType, Vec<&str>)> = None;
+
+        for line in doc {
+            let trimmed = line.trim();
+            // 代码块
+            if let Some((lang, lines)) = code.as_mut() {
+                if trimmed.starts_with("```") {
+                    let content = lines.join("\n");
+                    if section.starts_with("example") && block.example.is_none() {
+                        block.example = Some(content);
+                    } else {
+                        block.descriptions.push(Description { dtype: DescriptionType::Code(*lang, content.clone()), content });
+                    }
+                    code = None;
+                } else {
+                    lines.push(line.as_str());
+                }
+                continue;
+            }
+            if let Some(info) = trimmed.strip_prefix("```") {
+                Self::flush_paragraph(block, &section, &mut paragraph);
+                // rustdoc 中未注明语言（或只有 ignore/no_run 等属性）的代码块都是 Rust
+                let lang = info.split(',').next().unwrap_or("").trim();
+                let lang = match lang {
+                    "" | "rust" | "ignore" | "no_run" | "should_panic" | "compile_fail" => InputFileType::Rust,
+                    other => InputFileType::from_str(other).unwrap_or(InputFileType::None),
+                };
+                code = Some((lang, Vec::new()));
+                continue;
+            }
+            // 小节标题
+            if let Some(heading) = trimmed.strip_prefix('#') {
+                Self::flush_paragraph(block, &section, &mut paragraph);
+                let heading = heading.trim_start_matches('#').trim();
+                section = heading.to_lowercase();
+                if !matches!(section.as_str(), "arguments" | "parameters" | "returns" | "examples" | "example") {
+                    let content = format!("**{}:**", heading);
+                    block.descriptions.push(Description { dtype: DescriptionType::Text(content.clone()), content });
+                }
+                continue;
+            }
+            if trimmed.is_empty() {
+                Self::flush_paragraph(block, &section, &mut paragraph);
+                continue;
+            }
+            if section == "arguments" || section == "parameters" {
+                match RUST_ARG_ITEM_RE.captures(trimmed) {
+                    Some(caps) => {
+                        let name = caps[1].to_string();
+                        let type_name = param_types
+                            .iter()
+                            .find(|(n, _)| *n == name)
+                            .map(|(_, ty)| ty.clone())
+                            .unwrap_or_default();
+                        block.parameters.push(Parameter::new(name, type_name, caps[2].trim(), block.parameters.len()));
+                    }
+                    // 列表项的续行
+                    None => {
+                        if let Some(p) = block.parameters.last_mut() {
+                            p.description.push(' ');
+                            p.description.push_str(trimmed);
+                        }
+                    }
+                }
+                continue;
+            }
+            paragraph.push(trimmed);
+        }
+        Self::flush_paragraph(block, &section, &mut paragraph);
+    }
+
+    /// 一个段落结束：第一段作为 brief，`# Returns` 下作为返回值说明，其余作为文字描述
+    fn flush_paragraph(block: &mut DocBlock, section: &str, paragraph: &mut Vec<&str>) {
+        if paragraph.is_empty() {
+            return;
+        }
+        let text = paragraph.join(" ");
+        paragraph.clear();
+        if section.is_empty() && block.brief.is_empty() && block.descriptions.is_empty() {
+            block.brief = text;
+        } else if section == "returns" {
+            if block.ret_value.is_empty() {
+                block.ret_value.push(Parameter::new("", block.signature.ret.clone().unwrap_or_default(), "", 0));
+            }
+            let ret = &mut block.ret_value[0];
+            if !ret.description.is_empty() {
+                ret.description.push(' ');
+            }
+            ret.description.push_str(&text);
+        } else {
+            block.descriptions.push(Description { dtype: DescriptionType::Text(text.clone()), content: text });
+        }
+    }
+
+    /// 用文档注释与完整的声明（到 `{` 或 `;` 为止）创建 DocBlock
+    fn create_item_block(doc: &[String], decl: &str) -> DocBlock {
+        // 多行声明合并为一行：`fn f(\n    a: T,\n)` -> `fn f(a: T)`
+        let raw = decl
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .replace("( ", "(")
+            .replace(", )", ")")
+            .replace(" )", ")")
+            .trim_end_matches(',')
+            .to_string();
+        let mut block = DocBlock::new(InputFileType::Rust);
+        block.signature = Signature::parse_rust(&raw);
+        block.is_async = raw.split_whitespace().take_while(|w| *w != "fn").any(|w| w == "async");
+        block.is_local = !raw.starts_with("pub");
+        let param_types = raw
+            .find('(')
+            .zip(raw.rfind(')'))
+            .filter(|_| block.signature.keyword == "fn")
+            .map(|(l, r)| Self::parse_params(&raw[l + 1..r]))
+            .unwrap_or_default();
+        Self::apply_docs(&mut block, doc, &param_types);
+        block
+    }
+
+    /// `//!` 模块文档生成的文档块，名字在 name_anonymous_blocks 中按文件名补上
+    fn create_module_block(doc: &[String]) -> DocBlock {
+        let mut block = DocBlock::new(InputFileType::Rust);
+        block.signature = Signature { raw: "mod".to_string(), keyword: "mod".to_string(), ..Default::default() };
+        Self::apply_docs(&mut block, doc, &[]);
+        block
+    }
+}
+
+impl FileParser for RustFileParser {
+    fn parse_reader(&self, reader: &mut dyn BufRead) -> Vec<DocBlock> {
+        let mut blocks = Vec::new();
+        let mut doc = Vec::<String>::new();
+        let mut module_doc = Vec::<String>::new();
+        // 跨多行的声明：(文档注释, 已读取的声明文本)
+        let mut item: Option<(Vec<String>, String)> = None;
+
+        for line in reader.lines().map_while(Result::ok) {
+            if let Some((_, decl)) = item.as_mut() {
+                decl.push(' ');
+                decl.push_str(line.trim());
+                if let Some(end) = decl.find(['{', ';']) {
+                    let (doc, decl) = item.take().unwrap();
+                    blocks.push(Self::create_item_block(&doc, &decl[..end]));
+                }
+                continue;
+            }
+
+            if let Some((inner, text)) = Self::doc_text(&line) {
+                if inner {
+                    module_doc.push(text.to_string());
+                } else {
+                    doc.push(text.to_string());
+                }
+                continue;
+            }
+            if !module_doc.is_empty() {
+                blocks.push(Self::create_module_block(&std::mem::take(&mut module_doc)));
+            }
+
+            let code = line.trim();
+            // 属性与普通注释不打断文档注释与声明之间的关联
+            if code.starts_with("#[") || (code.starts_with("//") && !doc.is_empty()) {
+                continue;
+            }
+            if !doc.is_empty() && Self::is_item_start(code) {
+                let doc = std::mem::take(&mut doc);
+                match code.find(['{', ';']) {
+                    Some(end) => blocks.push(Self::create_item_block(&doc, &code[..end])),
+                    None => item = Some((doc, code.to_string())),
+                }
+                continue;
+            }
+            doc.clear();
+        }
+        if !module_doc.is_empty() {
+            blocks.push(Self::create_module_block(&module_doc));
+        }
+        blocks
+    }
+}
+
+/// Python 文件解析器
+///
+/// 识别 `def` / `async def` / `class` 声明（可跨多行，到顶层的 `:` 为止）之后紧跟的
+/// `"""` 或 `'''` 文档字符串。类体中的方法以类名为所属对象。文档字符串按 Google 风格
+/// （`Args:`、`Returns:`、`Yields:`、`Raises:`、`Attributes:`、`Example:`、`Note:` 小节）
+/// 或 reST 风格（`:param x:`、`:type x:`、`:returns:`、`:rtype:`、`:raises E:`）解析：
+/// - 第一段作为 brief，其余段落作为描述
+/// - 参数类型没有写在文档中时取自签名中的注解，返回值类型同理
+pub struct PythonFileParser;
+
+/// 匹配 Google 风格的小节标题 `Args:`、`Keyword Args:`
+static PY_SECTION_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^([A-Za-z]+(?: [A-Za-z]+)?):$").unwrap());
+
+/// 匹配 Google 风格的参数项 `name (type): desc` / `*args: desc`
+static PY_ARG_ITEM_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^\*{0,2}([A-Za-z_]\w*)\s*(?:\(([^)]*)\))?\s*:(?:\s+(.*))?$").unwrap()
+});
+
+/// 匹配 reST 风格的字段 `:param int x: desc` / `:returns: desc`
+static PY_REST_FIELD_RE: Lazy<Regex> = Lazy::new(|| Regex::new(r"^:(\w+)(?:\s+([^:]+))?:(?:\s+(.*))?$").unwrap());
+
+impl PythonFileParser {
+    /// 代码行是否以函数或类声明开头
+    fn is_def_start(code: &str) -> bool {
+        ["def ", "async def ", "class "].iter().any(|kw| code.starts_with(kw))
+    }
+
+    /// 解析参数列表（不含括号），返回 (名字, 注解类型)；`self`、`cls` 以及 `/`、`*` 分隔符也包含在内
+    /// 名字去掉 `*args` / `**kwargs` 的星号与默认值
+    pub fn parse_params(list: &str) -> Vec<(String, String)> {
+        RustFileParser::split_top_level(list)
+            .into_iter()
+            .filter_map(|param| {
+                let param = param.split_once('=').map_or(param, |(p, _)| p).trim();
+                let (name, ty) = param.split_once(':').unwrap_or((param, ""));
+                let name = name.trim().trim_start_matches('*');
+                (!name.is_empty() && name != "/").then(|| (name.to_string(), ty.trim().to_string()))
+            })
+            .collect()
+    }
+
+    /// 读取 lines[start..] 处紧跟在声明后的文档字符串（之间只允许空行）
+    /// 返回去掉公共缩进后的各行，以及文档字符串之后的下一行的下标
+    fn read_docstring(lines: &[String], start: usize) -> Option<(Vec<String>, usize)> {
+        let first = (start..lines.len()).find(|&i| !lines[i].trim().is_empty())?;
+        let code = lines[first].trim();
+        let code = code.strip_prefix(['r', 'R', 'u', 'U']).unwrap_or(code);
+        let quote = ["\"\"\"", "'''"].into_iter().find(|q| code.starts_with(q))?;
+        let rest = &code[3..];
+        // 单行文档字符串 """text"""
+        if let Some(end) = rest.find(quote) {
+            return Some((vec![rest[..end].trim().to_string()], first + 1));
+        }
+
+        let mut body = vec![rest.trim().to_string()];
+        let mut end = first + 1;
+        while end < lines.len() {
+            let line = lines[end].trim_end();
+            end += 1;
+            match line.find(quote) {
+                Some(close) => {
+                    body.push(line[..close].to_string());
+                    break;
+                }
+                None => body.push(line.to_string()),
+            }
+        }
+        // 第一行紧跟在引号之后，不参与公共缩进的计算
+        let indent = body[1..]
+            .iter()
+            .filter(|l| !l.trim().is_empty())
+            .map(|l| l.len() - l.trim_start().len())
+            .min()
+            .unwrap_or(0);
+        for line in body[1..].iter_mut() {
+            *line = line.get(indent..).unwrap_or("").trim_end().to_string();
+        }
+        while body.first().is_some_and(|l| l.is_empty()) {
+            body.remove(0);
+        }
+        while body.last().is_some_and(|l| l.is_empty()) {
+            body.pop();
+        }
+        Some((body, end))
+    }
+
+    fn new_parameter(name: &str, type_name: &str, description: &str, number: usize) -> Parameter {
+        // Google 风格把可选写在类型里：`(int, optional)`
+        let (type_name, optional) = match type_name.trim().strip_suffix("optional") {
+            Some(ty) => (ty.trim().trim_end_matches(',').trim(), true),
+            None => (type_name.trim(), false),
+        };
+        Parameter { optional, ..Parameter::new(name, type_name, description.trim(), number) }
+    }
+
+    /// `type: desc` 形式的返回值 / 产出值 / 异常项，类型部分不含空白时才当作类型
+    fn split_typed(text: &str) -> (&str, &str) {
+        match text.split_once(':') {
+            Some((ty, desc)) if !ty.is_empty() && !ty.contains(char::is_whitespace) => (ty, desc.trim()),
+            _ => ("", text),
+        }
+    }
+
+    /// 把 reST 字段 `:kind arg: text` 写入 block，返回其内容所属的小节（续行追加到该小节）
+    fn apply_rest_field(block: &mut DocBlock, kind: &str, arg: &str, text: &str) -> &'static str {
+        match kind {
+            "param" | "parameter" | "arg" | "argument" | "key" | "keyword" => {
+                // `:param int x:` 中最后一个词是参数名，前面的是类型
+                let (ty, name) = arg.rsplit_once(char::is_whitespace).unwrap_or(("", arg));
+                let number = block.parameters.len();
+                block.parameters.push(Self::new_parameter(name.trim_start_matches('*'), ty, text, number));
+                "args"
+            }
+            "type" => {
+                if let Some(p) = block.parameters.iter_mut().find(|p| p.name == arg.trim_start_matches('*')) {
+                    p.type_name = text.to_string();
+                }
+                ""
+            }
+            "returns" | "return" => {
+                Self::append_item(&mut block.ret_value, "", text, true);
+                "returns"
+            }
+            "rtype" => {
+                Self::append_item(&mut block.ret_value, "", "", true);
+                block.ret_value[0].type_name = text.to_string();
+                ""
+            }
+            "yields" | "yield" => {
+                Self::append_item(&mut block.yields, "", text, true);
+                "yields"
+            }
+            "raises" | "raise" | "except" | "exception" => {
+                Self::append_item(&mut block.errors, arg, text, false);
+                "raises"
+            }
+            _ => "",
+        }
+    }
+
+    /// 返回值只有一项（后续内容追加到同一项）；异常每行一项
+    fn append_item(items: &mut Vec<Parameter>, type_name: &str, text: &str, single: bool) {
+        if single && !items.is_empty() {
+            let item = &mut items[0];
+            if !text.is_empty() {
+                if !item.description.is_empty() {
+                    item.description.push(' ');
+                }
+                item.description.push_str(text);
+            }
+            if item.type_name.is_empty() {
+                item.type_name = type_name.to_string();
+            }
+            return;
+        }
+        let number = items.len();
+        items.push(Self::new_parameter("", type_name, text, number));
+    }
+
+    /// 把续行追加到最近一项的描述
+    fn append_continuation(items: &mut [Parameter], text: &str) {
+        if let Some(p) = items.last_mut() {
+            if !p.description.is_empty() {
+                p.description.push(' ');
+            }
+            p.description.push_str(text);
+        }
+    }
+
+    /// 把文档字符串写入 block；param_types 为签名中的 (参数名, 注解类型)
+    fn apply_docstring(block: &mut DocBlock, doc: &[String], param_types: &[(String, String)]) {
+        let mut section = "";
+        // 小节中第一项的缩进，缩进更深的行是上一项的续行
+        let mut item_indent: Option<usize> = None;
+        let mut paragraph: Vec<&str> = Vec::new();
+        let mut example: Vec<&str> = Vec::new();
+
+        for line in doc {
+            let trimmed = line.trim();
+            let indent = line.len() - line.trim_start().len();
+            if indent == 0 && !trimmed.is_empty() {
+                // 没有缩进的行结束当前小节
+                let heading = PY_SECTION_RE.captures(trimmed).map(|caps| caps[1].to_lowercase());
+                let next = match heading.as_deref() {
+                    Some("args" | "arguments" | "parameters" | "params" | "keyword args" | "keyword arguments") => "args",
+                    Some("returns" | "return") => "returns",
+                    Some("yields" | "yield") => "yields",
+                    Some("raises" | "raise" | "exceptions") => "raises",
+                    Some("attributes") => "attributes",
+                    Some("example" | "examples") => "example",
+                    Some("note" | "notes") => "note",
+                    _ => "",
+                };
+                if !next.is_empty() || !section.is_empty() {
+                    Self::flush_paragraph(block, section, &mut paragraph);
+                    section = next;
+                    item_indent = None;
+                }
+                if !next.is_empty() {
+                    continue;
+                }
+                if let Some(caps) = PY_REST_FIELD_RE.captures(trimmed) {
+                    Self::flush_paragraph(block, section, &mut paragraph);
+                    let arg = caps.get(2).map_or("", |m| m.as_str().trim());
+                    let text = caps.get(3).map_or("", |m| m.as_str().trim());
+                    section = Self::apply_rest_field(block, &caps[1], arg, text);
+                    item_indent = Some(0);
+                    continue;
+                }
+            }
+            if trimmed.is_empty() {
+                match section {
+                    "example" => example.push(""),
+                    _ => Self::flush_paragraph(block, section, &mut paragraph),
+                }
+                continue;
+            }
+
+            let is_item = indent <= *item_indent.get_or_insert(indent);
+            match section {
+                "args" | "attributes" => {
+                    let list = if section == "args" { &mut block.parameters } else { &mut block.fields };
+                    match PY_ARG_ITEM_RE.captures(trimmed).filter(|_| is_item) {
+                        Some(caps) => {
+                            let ty = caps.get(2).map_or("", |m| m.as_str());
+                            let desc = caps.get(3).map_or("", |m| m.as_str());
+                            let number = list.len();
+                            list.push(Self::new_parameter(&caps[1], ty, desc, number));
+                        }
+                        None => Self::append_continuation(list, trimmed),
+                    }
+                }
+                "returns" | "yields" => {
+                    let list = if section == "returns" { &mut block.ret_value } else { &mut block.yields };
+                    let (ty, desc) = if list.is_empty() { Self::split_typed(trimmed) } else { ("", trimmed) };
+                    Self::append_item(list, ty, desc, true);
+                }
+                "raises" if is_item => {
+                    let (ty, desc) = Self::split_typed(trimmed);
+                    Self::append_item(&mut block.errors, ty, desc, false);
+                }
+                "raises" => Self::append_continuation(&mut block.errors, trimmed),
+                "example" => example.push(line.get(item_indent.unwrap_or(0)..).unwrap_or(trimmed)),
+                _ => paragraph.push(trimmed),
+            }
+        }
+        Self::flush_paragraph(block, section, &mut paragraph);
+        while example.last().is_some_and(|l| l.is_empty()) {
+            example.pop();
+        }
+        if !example.is_empty() {
+            block.example = Some(example.join("\n"));
+        }
+
+        // 文档中没有写类型的参数与返回值使用签名中的注解
+        for p in block.parameters.iter_mut().filter(|p| p.type_name.is_empty()) {
+            if let Some((_, ty)) = param_types.iter().find(|(name, _)| *name == p.name) {
+                p.type_name = ty.clone();
+            }
+        }
+        if let (Some(ret), Some(ty)) = (block.ret_value.first_mut(), &block.signature.ret) {
+            if ret.type_name.is_empty() {
+                ret.type_name = ty.clone();
+            }
+        }
+    }
+
+    /// 一个段落结束：第一段作为 brief，Note 小节作为提示，其余作为文字描述
+    fn flush_paragraph(block: &mut DocBlock, section: &str, paragraph: &mut Vec<&str>) {
+        if paragraph.is_empty() {
+            return;
+        }
+        let text = paragraph.join(" ");
+        paragraph.clear();
+        if section == "note" {
+            block.note = text.clone();
+            block.descriptions.push(Description { dtype: DescriptionType::Note(text.clone()), content: text });
+        } else if block.brief.is_empty() && block.descriptions.is_empty() {
+            block.brief = text;
+        } else {
+            block.descriptions.push(Description { dtype: DescriptionType::Text(text.clone()), content: text });
+        }
+    }
+
+    /// 用文档字符串与声明（已合并为一行、去掉结尾的 `:`）创建 DocBlock，owner 为所在的类
+    fn create_block(doc: &[String], decl: &str, owner: Option<&str>) -> DocBlock {
+        let mut block = DocBlock::new(InputFileType::Python);
+        block.signature = Signature::parse_python(decl);
+        block.is_async = block.signature.keyword == "async def";
+        let name = &block.signature.name;
+        block.is_local = name.starts_with('_') && !(name.starts_with("__") && name.ends_with("__"));
+        if block.signature.keyword == "class" {
+            block.class = Some(name.clone());
+        }
+        let param_types = decl
+            .find('(')
+            .zip(decl.rfind(')'))
+            .filter(|(l, r)| l < r && block.signature.keyword != "class")
+            .map(|(l, r)| Self::parse_params(&decl[l + 1..r]))
+            .unwrap_or_default();
+        if let Some(owner) = owner {
+            block.signature.owner = owner.to_string();
+            block.owner_object = owner.to_string();
+            block.is_member = param_types.first().is_some_and(|(name, _)| name == "self");
+        }
+        Self::apply_docstring(&mut block, doc, &param_types);
+        block
+    }
+}
+
+impl FileParser for PythonFileParser {
+    fn parse_reader(&self, reader: &mut dyn BufRead) -> Vec<DocBlock> {
+        let lines: Vec<String> = reader.lines().map_while(Result::ok).collect();
+        let mut blocks = Vec::new();
+        // 包围当前行的类：(class 行的缩进, 类名)
+        let mut classes: Vec<(usize, String)> = Vec::new();
+        let mut i = 0;
+
+        while i < lines.len() {
+            let code = lines[i].trim_start();
+            if code.is_empty() || code.starts_with('#') {
+                i += 1;
+                continue;
+            }
+            let indent = lines[i].len() - code.len();
+            while classes.last().is_some_and(|(class_indent, _)| *class_indent >= indent) {
+                classes.pop();
+            }
+            if !Self::is_def_start(code) {
+                i += 1;
+                continue;
+            }
+
+            // 声明可跨多行，到括号外的 `:` 为止
+            let mut decl = String::new();
+            let mut depth = 0i32;
+            let mut end = i;
+            loop {
+                let part = lines[end].trim();
+                decl.push_str(part);
+                decl.push(' ');
+                depth += part.matches(['(', '[']).count() as i32 - part.matches([')', ']']).count() as i32;
+                if (depth <= 0 && part.ends_with(':')) || end + 1 >= lines.len() {
+                    break;
+                }
+                end += 1;
+            }
+            let decl = decl
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ")
+                .replace("( ", "(")
+                .replace(", )", ")")
+                .replace(" )", ")");
+            let decl = decl.trim_end_matches(':').trim_end();
+
+            let owner = classes.last().map(|(_, name)| name.clone());
+            if code.starts_with("class ") {
+                classes.push((indent, Signature::parse_python(decl).name));
+            }
+            match Self::read_docstring(&lines, end + 1) {
+                Some((doc, next)) => {
+                    blocks.push(Self::create_block(&doc, decl, owner.as_deref()));
+                    i = next;
+                }
+                None => i = end + 1,
+            }
+        }
+        blocks
+    }
+}
+
+/// JavaScript / TypeScript 文件解析器
+///
+/// 识别 `/** */` JSDoc 注释之后的声明：
+/// - `function` / `async function` / `function*`，可带 `export` / `export default`
+/// - `const f = (a, b) => ...`、`const f = function (...)` 等函数表达式
+/// - `class` 及类体中的方法（`name(...) {`、`static`、`async`、`get` / `set`），方法以类名为所属对象
+/// - 其他声明（`const x = 1`、`interface`、`type`）只记录签名
+///
+/// 第一段文字作为 brief，其余段落作为描述。`@param {Type} name desc` 中花括号包围的类型
+/// 去掉花括号后存入 type_name，`[name]` / `[name=默认值]` 表示可选参数；
+/// 文档没有写类型时使用 TypeScript 签名中的类型注解
+pub struct JsDocParser {
+    pub lang: InputFileType,
+}
+
+/// JSDoc 中只描述声明本身、不需要输出的标签
+const JS_IGNORED_TAGS: &[&str] = &[
+    "function", "func", "method", "memberof", "module", "type", "typedef", "callback", "constructor",
+    "class", "override", "readonly", "static", "abstract", "fileoverview", "file", "public", "exports",
+    "export", "inheritdoc", "protected", "instance", "name", "kind",
+];
+
+/// 匹配 JS 的函数声明 `async function* name(`，捕获 async 与名字
+static JS_FUNCTION_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(async\s+)?function\s*\*?\s*([A-Za-z_$][\w$]*)?\s*[<(]").unwrap()
+});
+
+/// 匹配变量声明 `const name = value`，捕获关键字、名字与值
+static JS_BINDING_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(const|let|var)\s+([A-Za-z_$][\w$]*)\s*(?::[^=]*)?=\s*(.*)$").unwrap()
+});
+
+/// 匹配 `class` / `interface` / `type` / `enum` 声明，捕获关键字与名字
+static JS_TYPE_DECL_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^(class|interface|type|enum)\s+([A-Za-z_$][\w$]*)").unwrap()
+});
+
+/// 匹配类体中的方法 `static async get name(`，捕获修饰符与名字
+static JS_METHOD_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"^((?:(?:public|private|protected|static|async|readonly|override|get|set)\s+)*)\*?\s*(#?[A-Za-z_$][\w$]*)\s*[<(]").unwrap()
+});
+
+impl JsDocParser {
+    /// 去掉声明前的 `export`、`export default`、`declare` 与 `abstract`
+    fn strip_modifiers(code: &str) -> &str {
+        let mut code = code.trim();
+        for prefix in ["export ", "default ", "declare ", "abstract "] {
+            if let Some(rest) = code.strip_prefix(prefix) {
+                code = rest.trim_start();
+            }
+        }
+        code
+    }
+
+    /// 一行代码中花括号深度的变化，忽略字符串与 `//` 注释中的花括号
+    fn brace_delta(code: &str) -> i32 {
+        let mut delta = 0;
+        let mut quote: Option<char> = None;
+        let mut chars = code.chars().peekable();
+        while let Some(c) = chars.next() {
+            match quote {
+                Some(q) => {
+                    if c == '\\' {
+                        chars.next();
+                    } else if c == q {
+                        quote = None;
+                    }
+                }
+                None => match c {
+                    '\'' | '"' | '`' => quote = Some(c),
+                    '/' if chars.peek() == Some(&'/') => break,
+                    '{' => delta += 1,
+                    '}' => delta -= 1,
+                    _ => {}
+                },
+            }
+        }
+        delta
+    }
+
+    /// 声明的头部：到括号外的 `{`、`;` 为止，箭头函数保留到 `=>`
+    fn declaration_head(code: &str) -> &str {
+        let mut depth = 0i32;
+        let mut prev = '\0';
+        for (i, c) in code.char_indices() {
+            match c {
+                '(' | '[' => depth += 1,
+                ')' | ']' => depth -= 1,
+                '>' if prev == '=' && depth == 0 => return code[..=i].trim(),
+                '{' | ';' if depth == 0 => return code[..i].trim().trim_end_matches('=').trim_end(),
+                _ => {}
+            }
+            prev = c;
+        }
+        code.trim()
+    }
+
+    /// 第一组括号中的参数列表与其后的返回类型注解 `(a: T): R`
+    fn params_and_return(code: &str) -> (Vec<(String, String)>, Option<String>) {
+        let open = match code.find('(') {
+            Some(open) => open,
+            None => {
+                // 单个参数的箭头函数 `x => x * 2`
+                let param = code.split("=>").next().unwrap_or("").trim();
+                let is_ident = !param.is_empty() && param.chars().all(|c| c.is_alphanumeric() || c == '_' || c == '$');
+                if code.contains("=>") && is_ident {
+                    return (vec![(param.to_string(), String::new())], None);
+                }
+                return (vec![], None);
+            }
+        };
+        let mut depth = 0i32;
+        let mut close = None;
+        for (i, c) in code[open..].char_indices() {
+            match c {
+                '(' => depth += 1,
+                ')' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        close = Some(open + i);
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let close = match close {
+            Some(close) => close,
+            None => return (vec![], None),
+        };
+        let params = RustFileParser::split_top_level(&code[open + 1..close])
+            .into_iter()
+            .map(|param| {
+                // 默认值 `= x` 不算类型，类型中的 `=>` 不是默认值
+                let default = param.char_indices().find(|&(i, c)| c == '=' && !param[i + 1..].starts_with(['>', '=']));
+                let param = default.map_or(param, |(i, _)| &param[..i]).trim();
+                let (name, ty) = match param.starts_with(['{', '[']) {
+                    // 解构参数没有单独的名字，整体作为名字
+                    true => (param, ""),
+                    false => param.split_once(':').unwrap_or((param, "")),
+                };
+                let name = name.trim().trim_start_matches("...").trim_end_matches('?');
+                (name.to_string(), ty.trim().to_string())
+            })
+            .collect();
+        let ret = code[close + 1..]
+            .trim()
+            .strip_prefix(':')
+            .map(|r| r.trim().trim_end_matches("=>").trim().to_string())
+            .filter(|r| !r.is_empty());
+        (params, ret)
+    }
+
+    /// 解析声明，返回签名、TypeScript 参数类型，以及是否是 async 函数 / 私有成员
+    fn parse_declaration(decl: &str, owner: Option<&str>) -> (Signature, Vec<(String, String)>, bool, bool) {
+        let head = Self::declaration_head(decl);
+        let code = Self::strip_modifiers(head);
+        let mut sig = Signature { raw: head.to_string(), ..Default::default() };
+        let mut params = vec![];
+        let (mut is_async, mut is_private) = (false, false);
+
+        if let Some(caps) = JS_FUNCTION_RE.captures(code) {
+            sig.keyword = "function".to_string();
+            sig.name = caps.get(2).map_or(String::new(), |m| m.as_str().to_string());
+            is_async = caps.get(1).is_some();
+            (params, sig.ret) = Self::params_and_return(&code[caps.get(0).unwrap().end() - 1..]);
+        } else if let Some(caps) = JS_TYPE_DECL_RE.captures(code) {
+            sig.keyword = caps[1].to_string();
+            sig.name = caps[2].to_string();
+        } else if let Some(caps) = JS_BINDING_RE.captures(code) {
+            sig.keyword = caps[1].to_string();
+            sig.name = caps[2].to_string();
+            let value = caps[3].trim();
+            is_async = value.starts_with("async");
+            let value = value.strip_prefix("async").unwrap_or(value).trim_start();
+            if value.starts_with("function") || value.contains("=>") {
+                let value = value.strip_prefix("function").unwrap_or(value);
+                (params, sig.ret) = Self::params_and_return(value);
+            }
+        } else if let Some(caps) = JS_METHOD_RE.captures(code).filter(|_| owner.is_some()) {
+            let modifiers: Vec<&str> = caps[1].split_whitespace().collect();
+            sig.keyword = "method".to_string();
+            sig.name = caps[2].to_string();
+            is_async = modifiers.contains(&"async");
+            is_private = modifiers.contains(&"private") || sig.name.starts_with('#');
+            if modifiers.contains(&"static") {
+                sig.keyword = "static".to_string();
+            }
+            (params, sig.ret) = Self::params_and_return(&code[caps.get(0).unwrap().end() - 1..]);
+        }
+        if let Some(owner) = owner {
+            sig.owner = owner.to_string();
+        }
+        sig.params = params.iter().map(|(name, _)| name.clone()).filter(|n| !n.is_empty()).collect();
+        (sig, params, is_async, is_private)
+    }
+
+    /// 解析 `{Type} name desc` / `{Type} [name=默认值] desc` / `{Type} desc`（named 为 false 时没有名字）
+    fn parse_tag_item(body: &str, named: bool, number: usize) -> Parameter {
+        let mut rest = body.trim();
+        let mut type_name = String::new();
+        if rest.starts_with('{') {
+            let mut depth = 0;
+            for (i, c) in rest.char_indices() {
+                match c {
+                    '{' => depth += 1,
+                    '}' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            type_name = rest[1..i].split_whitespace().collect::<Vec<_>>().join(" ");
+                            rest = rest[i + 1..].trim_start();
+                            break;
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+        let (mut name, mut optional, mut default) = (String::new(), false, None);
+        if named {
+            let end = match rest.starts_with('[') {
+                true => rest.find(']').map_or(rest.len(), |i| i + 1),
+                false => rest.find(char::is_whitespace).unwrap_or(rest.len()),
+            };
+            let token = &rest[..end];
+            rest = rest[end..].trim_start();
+            match token.strip_prefix('[').map(|t| t.trim_end_matches(']')) {
+                Some(inner) => {
+                    optional = true;
+                    let (n, d) = inner.split_once('=').map_or((inner, None), |(n, d)| (n, Some(d.trim())));
+                    name = n.trim().to_string();
+                    default = d.filter(|d| !d.is_empty()).map(str::to_string);
+                }
+                None => name = token.to_string(),
+            }
+        }
+        let rest = rest.strip_prefix('-').unwrap_or(rest);
+        let mut description = rest.split_whitespace().collect::<Vec<_>>().join(" ");
+        if let Some(default) = default {
+            description = match description.is_empty() {
+                true => format!("默认为 {}", default),
+                false => format!("{}（默认为 {}）", description, default),
+            };
+        }
+        Parameter { optional, ..Parameter::new(name, type_name, description, number) }
+    }
+
+    /// 用 JSDoc 注释内容与声明创建 DocBlock，owner 为所在的类
+    fn create_block(doc: &[String], decl: &str, owner: Option<&str>, lang: InputFileType) -> DocBlock {
+        // 第一个标签之前的文字：第一段是简介，其余段落是描述
+        let first_tag = doc.iter().position(|l| l.starts_with('@')).unwrap_or(doc.len());
+        let mut intro = doc[..first_tag].iter().map(String::as_str).skip_while(|l| l.is_empty());
+        let brief: Vec<&str> = intro.by_ref().take_while(|l| !l.is_empty()).collect();
+        let mut lines = Vec::new();
+        if !brief.is_empty() {
+            lines.push(format!("@brief {}", brief.join(" ")));
+        }
+        let rest: Vec<&str> = intro.collect();
+        if rest.iter().any(|l| !l.is_empty()) {
+            lines.push("@description".to_string());
+            lines.extend(rest.iter().map(|l| l.to_string()));
+        }
+
+        // 其余部分按标签切分，JSDoc 特有的标签在这里解析，其他的交给通用的标签解析
+        let mut params = Vec::new();
+        let mut properties = Vec::new();
+        let mut returns = Vec::new();
+        let mut yields = Vec::new();
+        let mut errors = Vec::new();
+        let mut generics = Vec::new();
+        let mut private = false;
+        let mut i = first_tag;
+        while i < doc.len() {
+            let end = doc[i + 1..].iter().position(|l| l.starts_with('@')).map_or(doc.len(), |p| i + 1 + p);
+            let (tag, _, body) = split_tag(&doc[i][1..]);
+            let continuation = doc[i + 1..end].iter().map(String::as_str);
+            let text = std::iter::once(body).chain(continuation).collect::<Vec<_>>().join(" ");
+            match tag {
+                "param" | "arg" | "argument" => params.push(Self::parse_tag_item(&text, true, params.len())),
+                "property" | "prop" => properties.push(Self::parse_tag_item(&text, true, properties.len())),
+                "returns" | "return" => returns.push(Self::parse_tag_item(&text, false, returns.len())),
+                "yields" | "yield" => yields.push(Self::parse_tag_item(&text, false, yields.len())),
+                "throws" | "exception" => errors.push(Self::parse_tag_item(&text, false, errors.len())),
+                "template" => generics.extend(body.split(',').map(str::trim).filter(|t| !t.is_empty()).map(str::to_string)),
+                "private" => private = true,
+                tag if JS_IGNORED_TAGS.contains(&tag) => {}
+                _ => lines.extend(doc[i..end].iter().cloned()),
+            }
+            i = end;
+        }
+
+        let mut block = LuaFileParser::create_docblock(lines, lang);
+        let (signature, param_types, is_async, is_private) = Self::parse_declaration(decl, owner);
+        // 文档没有写类型时使用 TypeScript 注解
+        for p in params.iter_mut() {
+            if p.type_name.is_empty() {
+                if let Some((_, ty)) = param_types.iter().find(|(name, _)| *name == p.name) {
+                    p.type_name = ty.clone();
+                }
+            }
+        }
+        if let (Some(ret), [r]) = (&signature.ret, returns.as_mut_slice()) {
+            if r.type_name.is_empty() {
+                r.type_name = ret.clone();
+            }
+        }
+        block.parameters = params;
+        block.properties = properties;
+        block.ret_value = returns;
+        block.yields = yields;
+        block.errors = errors;
+        block.generics = generics;
+        block.is_async |= is_async;
+        block.is_local = private || is_private;
+        if signature.keyword == "class" {
+            block.class = Some(signature.name.clone());
+        }
+        if let Some(owner) = owner {
+            block.owner_object = owner.to_string();
+            block.is_member = signature.keyword == "method";
+        }
+        block.signature = signature;
+        block
+    }
+}
+
+impl FileParser for JsDocParser {
+    fn parse_reader(&self, reader: &mut dyn BufRead) -> Vec<DocBlock> {
+        let mut blocks = Vec::new();
+        // 尚未被声明消费的 JSDoc 内容
+        let mut doc = Vec::<String>::new();
+        let mut in_doc_comment = false;
+        let mut in_plain_comment = false;
+        // 跨多行的声明：(文档注释, 已读取的声明文本, 括号深度, 所在的类)
+        let mut decl: Option<(Vec<String>, String, i32, Option<String>)> = None;
+        // 包围当前行的类：(类体所在的花括号深度, 类名)
+        let mut classes: Vec<(i32, String)> = Vec::new();
+        let mut depth = 0i32;
+
+        for line in reader.lines().map_while(Result::ok) {
+            if in_doc_comment {
+                doc.push(CFileParser::comment_text(&line));
+                in_doc_comment = !line.contains("*/");
+                continue;
+            }
+            if in_plain_comment {
+                in_plain_comment = !line.contains("*/");
+                continue;
+            }
+            let code = line.trim();
+            if code.starts_with("/**") && !code.starts_with("/**/") {
+                doc.clear();
+                doc.push(CFileParser::comment_text(code));
+                in_doc_comment = !code.contains("*/");
+                continue;
+            }
+            if code.starts_with("/*") {
+                in_plain_comment = !code.contains("*/");
+                continue;
+            }
+            if code.starts_with("//") {
+                continue;
+            }
+            if code.is_empty() && decl.is_none() {
+                doc.clear();
+                continue;
+            }
+
+            let before = depth;
+            depth += Self::brace_delta(code);
+            // 多行声明：读到括号闭合为止
+            if let Some((_, text, parens, _)) = decl.as_mut() {
+                text.push(' ');
+                text.push_str(code);
+                *parens += code.matches('(').count() as i32 - code.matches(')').count() as i32;
+                if *parens <= 0 {
+                    let (doc, text, _, owner) = decl.take().unwrap();
+                    blocks.push(Self::create_block(&doc, &text, owner.as_deref(), self.lang));
+                }
+            } else {
+                // 类体第一层的声明是类的成员
+                let owner = classes.last().filter(|(d, _)| *d == before).map(|(_, name)| name.clone());
+                let stripped = Self::strip_modifiers(code);
+                if let Some(caps) = JS_TYPE_DECL_RE.captures(stripped).filter(|c| &c[1] == "class") {
+                    classes.push((before + 1, caps[2].to_string()));
+                }
+                if !doc.is_empty() {
+                    let doc = std::mem::take(&mut doc);
+                    let parens = code.matches('(').count() as i32 - code.matches(')').count() as i32;
+                    match parens > 0 {
+                        true => decl = Some((doc, code.to_string(), parens, owner)),
+                        false => blocks.push(Self::create_block(&doc, code, owner.as_deref(), self.lang)),
+                    }
                 }
-                Err(_) => continue,
+            }
+            // 类体结束
+            while classes.last().is_some_and(|(d, _)| before >= *d && depth < *d) {
+                classes.pop();
             }
         }
-
-        return doc_blocks;
-    }
-}
-
-/// C 文件解析器示例
-pub struct CFileParser;
-impl FileParser for CFileParser {
-    fn parse(&self, _file: &File) -> Vec<DocBlock> {
-        vec![]
+        blocks
     }
 }
 
 pub struct NoneFileParser;
 impl FileParser for NoneFileParser {
-    fn parse(&self, _file: &File) -> Vec<DocBlock> {
+    fn parse_reader(&self, _reader: &mut dyn BufRead) -> Vec<DocBlock> {
         vec![]
     }
 }
@@ -587,32 +3600,206 @@ pub fn create_file_parser(optkind: &Option<InputFileType>) -> Box<dyn FileParser
     match kind {
         InputFileType::Lua => Box::new(LuaFileParser {}),
         InputFileType::C => Box::new(CFileParser {}),
-        InputFileType::Cpp => {
-            println!("not supported code file = {:?}", kind.to_str());
-            Box::new(CFileParser {})
+        InputFileType::Python => Box::new(PythonFileParser {}),
+        InputFileType::JavaScript | InputFileType::TypeScript => Box::new(JsDocParser { lang: *kind }),
+        InputFileType::Cpp => Box::new(CppFileParser {}),
+        InputFileType::Rust => Box::new(RustFileParser {}),
+        InputFileType::None => {
+            log::debug!("未知的文件类型，不解析");
+            Box::new(NoneFileParser {})
         }
-        InputFileType::Rust => {
-            println!("not supported code file = {:?}", kind.to_str());
-            Box::new(CFileParser {})
+    }
+}
+
+/// 生成与 GitHub 标题锚点一致的 slug：转小写，去掉标点，空格换成 `-`
+pub fn anchor_slug(text: &str) -> String {
+    text.trim()
+        .to_lowercase()
+        .chars()
+        .filter_map(|c| match c {
+            ' ' => Some('-'),
+            c if c.is_alphanumeric() || c == '-' || c == '_' => Some(c),
+            _ => None,
+        })
+        .collect()
+}
+
+/// 为每个文档块生成唯一的锚点，同名函数依次追加 `-1`、`-2` 后缀
+pub fn unique_anchors<'a>(blocks: impl IntoIterator<Item = &'a DocBlock>) -> Vec<String> {
+    let mut seen = std::collections::HashMap::<String, usize>::new();
+    blocks
+        .into_iter()
+        .map(|block| {
+            let slug = anchor_slug(&block.function_name());
+            let count = seen.entry(slug.clone()).or_insert(0);
+            let anchor = if *count == 0 { slug } else { format!("{}-{}", slug, count) };
+            *count += 1;
+            anchor
+        })
+        .collect()
+}
+
+/// 转义 Markdown 表格单元格中的 `|` 并把换行替换为 `<br>`，避免破坏表格结构
+fn escape_table_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
+
+/// 转义纯文本中会被 Markdown 解释的字符（`*` `_` `` ` `` `[` `]` `<`），使其按原样显示
+///
+/// 成对反引号之间的代码片段（`` `init()` ``，或等长的多个反引号包围）原样保留；
+/// 没有配对的反引号照常转义
+pub fn escape_markdown(text: &str) -> Cow<'_, str> {
+    const SPECIAL: [char; 6] = ['*', '_', '`', '[', ']', '<'];
+    if !text.contains(SPECIAL) {
+        return Cow::Borrowed(text);
+    }
+    let mut escaped = String::with_capacity(text.len() + 8);
+    let mut rest = text;
+    while let Some(c) = rest.chars().next() {
+        if c == '`' {
+            let ticks = rest.len() - rest.trim_start_matches('`').len();
+            if let Some(len) = code_span_len(rest, ticks) {
+                escaped.push_str(&rest[..len]);
+                rest = &rest[len..];
+            } else {
+                for _ in 0..ticks {
+                    escaped.push_str("\\`");
+                }
+                rest = &rest[ticks..];
+            }
+            continue;
         }
-        InputFileType::Python => {
-            println!("not supported code file = {:?}", kind.to_str());
-            Box::new(CFileParser {})
+        if SPECIAL.contains(&c) {
+            escaped.push('\\');
         }
-        InputFileType::None => {
-            println!("not supported code file = {:?}", kind.to_str());
-            Box::new(NoneFileParser {})
+        escaped.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    Cow::Owned(escaped)
+}
+
+/// text 以 ticks 个反引号开头时，到等长的结束反引号为止的代码片段长度；没有配对时返回 None
+fn code_span_len(text: &str, ticks: usize) -> Option<usize> {
+    let mut pos = ticks;
+    while let Some(start) = text[pos..].find('`').map(|i| pos + i) {
+        let run = text[start..].len() - text[start..].trim_start_matches('`').len();
+        if run == ticks {
+            return Some(start + run);
         }
+        pos = start + run;
     }
+    None
 }
 
-/// 简单的 Markdown 格式化器示例
-pub struct MarkdownFormatter {}
+/// 文档块的渲染风格
+#[derive(Debug, Clone, Copy, PartialEq, Default, clap::ValueEnum)]
+pub enum Style {
+    /// 完整的分节输出
+    #[default]
+    Full,
+    /// 速查表风格：签名加一行参数/返回值摘要
+    Compact,
+}
+
+/// 参数列表的渲染方式
+#[derive(Debug, Clone, Copy, PartialEq, Default, clap::ValueEnum)]
+pub enum ParamStyle {
+    /// 每个参数一行 `- name (type): desc`
+    #[default]
+    List,
+    /// `| Name | Type | Description |` 表格
+    Table,
+}
+
+/// Markdown 格式化器
+#[derive(Clone)]
+pub struct MarkdownFormatter {
+    /// 是否在签名下方输出元信息徽章行 (since/async/...)
+    pub badges: bool,
+    /// 是否为每个函数输出带锚点的标题，并在文件有多个文档块时于开头输出目录
+    pub file_toc: bool,
+    /// 所有标题级别整体下移的层数，便于嵌入到已有标题结构的文档中
+    pub heading_offset: usize,
+    /// 是否输出签名代码块；关闭时以函数名标题代替
+    pub signature: bool,
+    /// 渲染风格
+    pub style: Style,
+    /// 参数列表的渲染方式
+    pub param_style: ParamStyle,
+    /// 是否在 format_file 的输出开头加上 YAML front-matter
+    pub front_matter: bool,
+    /// 是否转义简介、描述等纯文本中的 Markdown 特殊字符；关闭时按作者写的 Markdown 原样输出。
+    /// Rust 文档注释总是按 Markdown 原样输出
+    pub escape_text: bool,
+    /// 是否把方法隐含的 self 作为第一个参数列出；关闭时在参数之前单独输出一行 Receiver
+    pub self_param: bool,
+    /// 是否输出私有的文档块（is_local：Lua 的 local 函数、C 的 static 函数等），默认省略
+    pub include_private: bool,
+}
+
+impl Default for MarkdownFormatter {
+    fn default() -> Self {
+        MarkdownFormatter {
+            badges: true,
+            file_toc: true,
+            heading_offset: 0,
+            signature: true,
+            style: Style::Full,
+            param_style: ParamStyle::List,
+            escape_text: true,
+            front_matter: false,
+            self_param: false,
+            include_private: false,
+        }
+    }
+}
 
 impl MarkdownFormatter {
-    /// 格式化函数签名
-    fn format_signature(&self, signature: &str) -> String {
-        format!("```lua\n{}\n```\n", signature)
+    /// 生成一行标题，级别加上 heading_offset 后最多为 6
+    pub fn heading(&self, level: usize, text: &str) -> String {
+        let level = (level + self.heading_offset).min(6);
+        format!("{} {}\n\n", "#".repeat(level), text)
+    }
+
+    /// 纯文本内容（简介、描述等），按 escape_text 决定是否转义；代码与公式不经过这里
+    fn text<'a>(&self, text: &'a str) -> Cow<'a, str> {
+        if self.escape_text {
+            escape_markdown(text)
+        } else {
+            Cow::Borrowed(text)
+        }
+    }
+
+    /// 格式化函数签名，代码块语言取自文档块的源文件语言
+    fn format_signature(&self, block: &DocBlock) -> String {
+        format!("```{}\n{}\n```\n", block.lang.fence_name(), block.signature.raw)
+    }
+
+    /// 把简短的元信息汇总为签名下方的一行徽章
+    fn format_badges(&self, block: &DocBlock) -> String {
+        if !self.badges {
+            return String::new();
+        }
+        let mut badges = Vec::new();
+        if let Some(since) = &block.since {
+            badges.push(format!("since {}", since));
+        }
+        if block.is_async {
+            badges.push("async".to_string());
+        }
+        if block.deprecated.is_some() {
+            badges.push("deprecated".to_string());
+        }
+        if let Some(complexity) = &block.complexity {
+            badges.push(format!("complexity {}", complexity));
+        }
+        if block.is_local {
+            badges.push("(local)".to_string());
+        }
+        if badges.is_empty() {
+            return String::new();
+        }
+        format!("🏷️ {}\n\n", badges.join(" · "))
     }
 
     /// 格式化 Includes
@@ -628,7 +3815,46 @@ impl MarkdownFormatter {
         if brief.is_empty() {
             return String::new();
         }
-        format!("**Brief:** {}\n\n", brief)
+        format!("**Brief:** {}\n\n", self.text(brief))
+    }
+
+    /// 格式化别名
+    fn format_aliases(&self, aliases: &[String]) -> String {
+        if aliases.is_empty() {
+            return String::new();
+        }
+        let names: Vec<String> = aliases.iter().map(|a| format!("`{}`", a)).collect();
+        format!("**Also available as:** {}\n\n", names.join(", "))
+    }
+
+    /// 格式化类型参数，约束写在名字之后：`- `K`: Base`
+    fn format_generics(&self, generics: &[String]) -> String {
+        if generics.is_empty() {
+            return String::new();
+        }
+        let mut s = String::from("**Type parameters:**\n");
+        for decl in generics {
+            match decl.split_once(": ") {
+                Some((name, bound)) => s.push_str(&format!("- `{}`: {}\n", name, bound)),
+                None => s.push_str(&format!("- `{}`\n", decl)),
+            }
+        }
+        s.push('\n');
+        s
+    }
+
+    /// 格式化方法的接收者，例如 "**Receiver:** `self` (Account)"
+    fn format_receiver(&self, receiver: Option<&Parameter>) -> String {
+        let receiver = match receiver {
+            Some(r) => r,
+            None => return String::new(),
+        };
+        let mut s = format!("**Receiver:** `{}` ({})", receiver.name, receiver.type_name);
+        if !receiver.description.is_empty() {
+            s.push_str(&format!(": {}", self.text(&receiver.description)));
+        }
+        s.push_str("\n\n");
+        s
     }
 
     /// 格式化参数列表
@@ -636,21 +3862,228 @@ impl MarkdownFormatter {
         if params.is_empty() {
             return String::new();
         }
+        if self.param_style == ParamStyle::Table {
+            return self.format_parameter_table(params);
+        }
         let mut s = String::from("**Parameters:**\n");
         for p in params {
             use std::fmt::Write;
-            let _ = writeln!(s, "- {} ({}): {}", p.name, p.type_name, p.description);
+            let direction = p.direction.as_ref().map(|d| format!(" ({})", d)).unwrap_or_default();
+            let variadic = if p.is_variadic() { " *(variadic)*" } else { "" };
+            let name = Self::param_name(p);
+            let optional = if p.optional { ", optional" } else { "" };
+            let _ = match (p.type_name.is_empty(), p.description.is_empty()) {
+                // 未写 @param 的参数只列出名字
+                (true, true) => writeln!(s, "- {}{}{}", name, direction, variadic),
+                _ => writeln!(
+                    s,
+                    "- {} ({}{}){}: {}{}",
+                    name,
+                    p.type_name,
+                    optional,
+                    direction,
+                    self.text(&p.description),
+                    variadic
+                ),
+            };
+            // 子标签内容缩进到参数项之下
+            for detail in &p.details {
+                for line in self.format_description_item(detail).lines() {
+                    let _ = writeln!(s, "  {}", line);
+                }
+            }
+        }
+        s.push('\n');
+        s
+    }
+
+    /// 参数名；可变参数 `...` 写成行内代码，避免被当作省略号
+    fn param_name(p: &Parameter) -> String {
+        if p.is_variadic() {
+            "`...`".to_string()
+        } else {
+            p.name.clone()
+        }
+    }
+
+    /// 以表格列出参数，方向写在名字后，子标签内容接在描述之后
+    fn format_parameter_table(&self, params: &[Parameter]) -> String {
+        let mut s = String::from("**Parameters:**\n\n| Name | Type | Description |\n| --- | --- | --- |\n");
+        for p in params {
+            let mut name = Self::param_name(p);
+            if let Some(d) = &p.direction {
+                name.push_str(&format!(" ({})", d));
+            }
+            if p.is_variadic() {
+                name.push_str(" (variadic)");
+            }
+            if p.optional {
+                name.push_str(" (optional)");
+            }
+            let mut description = self.text(&p.description).into_owned();
+            for detail in &p.details {
+                description.push('\n');
+                // 表格单元格中放不下代码块，改用行内代码
+                match detail.dtype {
+                    DescriptionType::Code(..) => description.push_str(&format!("`{}`", detail.content)),
+                    _ => description.push_str(self.format_description_item(detail).trim()),
+                }
+            }
+            s.push_str(&format!(
+                "| {} | {} | {} |\n",
+                escape_table_cell(&name),
+                escape_table_cell(&p.type_name),
+                escape_table_cell(description.trim())
+            ));
+        }
+        s.push('\n');
+        s
+    }
+
+    /// 格式化属性表
+    fn format_properties(&self, props: &[Parameter]) -> String {
+        self.format_member_table("Properties", props)
+    }
+
+    /// 格式化表/结构体/枚举的字段，每个字段一行：`- name (type): desc`
+    fn format_fields(&self, fields: &[Parameter]) -> String {
+        if fields.is_empty() {
+            return String::new();
+        }
+        let mut s = String::from("**Fields:**\n");
+        for p in fields {
+            s.push_str("- ");
+            s.push_str(&p.name);
+            if !p.type_name.is_empty() {
+                s.push_str(&format!(" ({})", p.type_name));
+            }
+            if !p.description.is_empty() {
+                s.push_str(&format!(": {}", self.text(&p.description)));
+            }
+            s.push('\n');
+        }
+        s.push('\n');
+        s
+    }
+
+    /// 以 `| Name | Type | Description |` 表格列出成员
+    fn format_member_table(&self, title: &str, members: &[Parameter]) -> String {
+        if members.is_empty() {
+            return String::new();
+        }
+        let mut s = format!("**{}:**\n\n| Name | Type | Description |\n| --- | --- | --- |\n", title);
+        for p in members {
+            s.push_str(&format!(
+                "| {} | {} | {} |\n",
+                escape_table_cell(&p.name),
+                escape_table_cell(&p.type_name),
+                escape_table_cell(&self.text(&p.description))
+            ));
         }
         s.push('\n');
         s
     }
 
-    /// 格式化返回值
-    fn format_return(&self, ret: &Option<Parameter>) -> String {
-        match ret {
-            Some(p) => format!(
+    /// 格式化返回值：只有一个时写在同一行，多个时列为列表
+    /// 多个返回值按 number 标为 Return 1、Return 2……
+    fn format_return(&self, rets: &[Parameter]) -> String {
+        match rets {
+            [] => String::new(),
+            [p] if p.is_variadic() => format!("**Returns:** ... (variadic): {}\n\n", self.text(&p.description)),
+            [p] => format!(
                 "**Returns:** {} ({}): {}\n\n",
-                p.name, p.type_name, p.description
+                p.name, p.type_name, self.text(&p.description)
+            ),
+            _ => {
+                let mut s = String::from("**Returns:**\n");
+                for p in rets {
+                    let label = p.number + 1;
+                    match p.is_variadic() {
+                        true => s.push_str(&format!("- Return {} ... (variadic): {}\n", label, self.text(&p.description))),
+                        false => s.push_str(&format!(
+                            "- Return {} ({}): {}\n",
+                            label, p.type_name, self.text(&p.description)
+                        )),
+                    }
+                }
+                s.push('\n');
+                s
+            }
+        }
+    }
+
+    /// 格式化协程/生成器产出的值，与返回值分开列出
+    fn format_yields(&self, yields: &[Parameter]) -> String {
+        if yields.is_empty() {
+            return String::new();
+        }
+        let mut s = String::from("**Yields:**\n");
+        for p in yields {
+            s.push_str(&format!("- ({}): {}\n", p.type_name, self.text(&p.description)));
+        }
+        s.push('\n');
+        s
+    }
+
+    /// 格式化可能抛出的错误，每个错误条件一行
+    fn format_errors(&self, errors: &[Parameter]) -> String {
+        if errors.is_empty() {
+            return String::new();
+        }
+        let mut s = String::from("**Errors:**\n");
+        for p in errors {
+            match (p.type_name.is_empty(), p.description.is_empty()) {
+                (true, _) => s.push_str(&format!("- {}\n", self.text(&p.description))),
+                (false, true) => s.push_str(&format!("- ({})\n", p.type_name)),
+                (false, false) => s.push_str(&format!("- ({}): {}\n", p.type_name, self.text(&p.description))),
+            }
+        }
+        s.push('\n');
+        s
+    }
+
+    /// 格式化 @see：同一输出中存在的函数链接到其锚点，其余只显示为代码
+    fn format_see(&self, see: &[String], anchors: &HashMap<String, String>) -> String {
+        if see.is_empty() {
+            return String::new();
+        }
+        let items: Vec<String> = see
+            .iter()
+            .map(|name| match anchors.get(name) {
+                Some(anchor) => format!("[`{}`](#{})", name, anchor),
+                None => format!("`{}`", name),
+            })
+            .collect();
+        format!("**See also:** {}\n\n", items.join(", "))
+    }
+
+    /// 作者与版本组成的一行页脚，例如 "*Author: Jane · Version: 1.2.0*"
+    fn format_metadata(&self, block: &DocBlock) -> String {
+        let mut items = Vec::new();
+        if let Some(author) = &block.author {
+            items.push(format!("Author: {}", self.text(author)));
+        }
+        if let Some(version) = &block.version {
+            items.push(format!("Version: {}", self.text(version)));
+        }
+        if items.is_empty() {
+            return String::new();
+        }
+        format!("*{}*\n\n", items.join(" · "))
+    }
+
+    /// 格式化待办事项
+    fn format_todos(&self, todos: &[String]) -> String {
+        todos.iter().map(|t| format!("> **TODO:** {}\n\n", t)).collect()
+    }
+
+    /// 格式化示例代码
+    fn format_example(&self, block: &DocBlock) -> String {
+        match &block.example {
+            Some(code) => format!(
+                "**Example:**\n\n```{}\n{}\n```\n\n",
+                block.lang.fence_name(),
+                code.trim_end()
             ),
             None => String::new(),
         }
@@ -659,29 +4092,30 @@ impl MarkdownFormatter {
     /// 格式化单个描述项
     fn format_description_item(&self, desc: &Description) -> String {
         match &desc.dtype {
-            DescriptionType::Text(_) => format!("{}\n", desc.content),
+            DescriptionType::Text(_) => format!("{}\n", self.text(&desc.content)),
             DescriptionType::Code(lang, _) => {
-                let lang_str = lang.to_str().unwrap_or("");
-                format!("```{}\n{}\n```\n", lang_str, desc.content)
+                format!("```{}\n{}\n```\n", lang.fence_name(), desc.content)
             }
             DescriptionType::MathFormula(ft, _) => match ft {
                 FormulaType::Inline => format!("${}$\n", desc.content),
                 FormulaType::Block => format!("$$\n{}\n$$\n", desc.content),
             },
-            DescriptionType::BulletList(_, _) => {
-                // 如果内容本身不包含 '- ' 前缀，则补上
+            DescriptionType::BulletList(level, _) => {
+                // 如果内容本身不包含 '- ' 前缀，则补上；每级嵌套缩进两个空格
                 let content = desc.content.trim();
                 let prefix = if content.starts_with("-") {
                     ""
                 } else {
                     "- "
                 };
-                format!("{}{}\n", prefix, content)
+                format!("{}{}{}\n", "  ".repeat((*level).max(0) as usize), prefix, content)
             }
             DescriptionType::HTMLLink(_) => {
                 // [link](url) - 这里假设 content 是 url
                 format!("[{}]({})\n", desc.content, desc.content)
             }
+            // 前后空行使引用块不与相邻的描述文字合并
+            DescriptionType::Note(_) => format!("\n> **Note:** {}\n\n", self.text(&desc.content)),
         }
     }
 
@@ -698,51 +4132,411 @@ impl MarkdownFormatter {
         s
     }
 
+    /// 签名代码块（关闭时改为函数名标题；file_toc 已输出过标题则不重复）
+    fn format_signature_or_name(&self, block: &DocBlock) -> String {
+        if self.signature {
+            self.format_signature(block)
+        } else if !self.file_toc {
+            self.heading(3, &block.function_name())
+        } else {
+            String::new()
+        }
+    }
+
+    /// 单行的参数与返回值摘要：`params: x:number, y:number → number`
+    fn format_param_summary(&self, block: &DocBlock) -> String {
+        if block.parameters.is_empty() && block.ret_value.is_empty() {
+            return String::new();
+        }
+        let params: Vec<String> = block
+            .parameters
+            .iter()
+            .map(|p| match p.type_name.is_empty() {
+                true => p.name.clone(),
+                false => format!("{}:{}", p.name, p.type_name),
+            })
+            .collect();
+        let mut s = format!(
+            "params: {}",
+            if params.is_empty() { "(none)".to_string() } else { params.join(", ") }
+        );
+        if !block.ret_value.is_empty() {
+            let rets: Vec<&str> = block
+                .ret_value
+                .iter()
+                .map(|ret| if ret.is_variadic() { "..." } else { ret.type_name.as_str() })
+                .collect();
+            s.push_str(" → ");
+            s.push_str(&rets.join(", "));
+        }
+        s.push_str("\n\n");
+        s
+    }
+
+    /// 速查表风格：签名、参数摘要行与简介
+    fn format_compact_block(&self, block: &DocBlock) -> String {
+        let mut s = self.format_signature_or_name(block);
+        s.push_str(&self.format_param_summary(block));
+        if !block.brief.is_empty() {
+            s.push_str(&format!("{}\n\n", self.text(&block.brief)));
+        }
+        s
+    }
+
+    /// 已弃用的函数在最前面输出醒目的提示
+    fn format_deprecated(&self, deprecated: &Option<String>) -> String {
+        match deprecated.as_deref() {
+            None => String::new(),
+            Some("") => "> ⚠️ **Deprecated**\n\n".to_string(),
+            Some(message) => format!("> ⚠️ **Deprecated:** {}\n\n", message),
+        }
+    }
+
     /// 格式化单个 DocBlock
-    fn format_block(&self, block: &DocBlock) -> String {
-        let mut s = String::new();
-        
+    ///
+    /// anchors 为同一输出中函数名（及别名）到锚点的映射，用于 @see 的链接
+    fn format_block(&self, block: &DocBlock, anchors: &HashMap<String, String>) -> String {
+        // rustdoc 注释本身就是 Markdown（解析器也会生成 `**Panics:**` 这样的小节标题），不转义
+        if self.escape_text && block.lang == InputFileType::Rust {
+            let raw = MarkdownFormatter { escape_text: false, ..self.clone() };
+            return raw.format_block(block, anchors);
+        }
+        let mut s = self.format_deprecated(&block.deprecated);
+        if self.style == Style::Compact {
+            s.push_str(&self.format_compact_block(block));
+            return s;
+        }
+
         // 1. Signature
-        s.push_str(&self.format_signature(&block.signature));
+        s.push_str(&self.format_signature_or_name(block));
+        s.push_str(&self.format_badges(block));
 
         // 2. Includes
         s.push_str(&self.format_includes(&block.includes));
 
         // 3. Brief
         s.push_str(&self.format_brief(&block.brief));
+        s.push_str(&self.format_aliases(&block.aliases));
 
         // 4. Parameters
-        s.push_str(&self.format_parameters(&block.parameters));
+        s.push_str(&self.format_generics(&block.generics));
+        match &block.receiver {
+            Some(receiver) if self.self_param => {
+                let params: Vec<Parameter> =
+                    std::iter::once(receiver).chain(&block.parameters).cloned().collect();
+                s.push_str(&self.format_parameters(&params));
+            }
+            receiver => {
+                s.push_str(&self.format_receiver(receiver.as_ref()));
+                s.push_str(&self.format_parameters(&block.parameters));
+            }
+        }
 
         // 5. Returns
         s.push_str(&self.format_return(&block.ret_value));
+        s.push_str(&self.format_yields(&block.yields));
+        s.push_str(&self.format_errors(&block.errors));
 
-        // 6. Detailed Descriptions
+        // 6. Properties
+        s.push_str(&self.format_properties(&block.properties));
+        s.push_str(&self.format_fields(&block.fields));
+
+        // 7. Detailed Descriptions
         s.push_str(&self.format_descriptions(&block.descriptions));
 
+        // 8. Example
+        s.push_str(&self.format_example(block));
+
+        // 9. See also
+        s.push_str(&self.format_see(&block.see, anchors));
+
+        // 10. TODO
+        s.push_str(&self.format_todos(&block.todos));
+
+        // 11. Author / Version
+        s.push_str(&self.format_metadata(block));
+
+        s
+    }
+
+    /// 格式化目录，每一项链接到对应函数的标题锚点
+    fn format_toc(&self, blocks: &[&DocBlock], anchors: &[String]) -> String {
+        let mut s = self.heading(2, "Contents");
+        for (block, anchor) in blocks.iter().zip(anchors) {
+            s.push_str(&format!("- [{}](#{})\n", block.function_name(), anchor));
+        }
+        s.push('\n');
+        s
+    }
+
+    /// 只含签名与简介的索引，每项链接到 target 中对应函数的锚点（不输出函数标题时只链接到 target）
+    ///
+    /// 文档块的顺序与锚点和 format 生成 target 时一致
+    pub fn format_index(&self, content: &[DocBlock], target: &str) -> String {
+        let visible: Vec<&DocBlock> = content.iter().filter(|b| self.include_private || !b.is_local).collect();
+        let ordered: Vec<&DocBlock> = self
+            .group_sections(&visible)
+            .into_iter()
+            .flat_map(|(_, blocks)| blocks)
+            .collect();
+        let anchors = unique_anchors(ordered.iter().copied());
+        let mut s = String::new();
+        for (block, anchor) in ordered.iter().zip(&anchors) {
+            let link = match self.file_toc {
+                true => format!("{}#{}", target, anchor),
+                false => target.to_string(),
+            };
+            s.push_str(&format!("- [`{}`]({})", block.signature.raw.trim(), link));
+            if !block.brief.is_empty() {
+                let brief = match block.lang {
+                    InputFileType::Rust => Cow::Borrowed(block.brief.as_str()),
+                    _ => self.text(&block.brief),
+                };
+                s.push_str(&format!(": {}", brief));
+            }
+            s.push('\n');
+        }
+        s.push('\n');
         s
     }
 
-    pub fn format(&self, content: Vec<DocBlock>) -> Result<String, fmt::Error> {
+    /// 按标题把文档块分节，各节按首次出现的顺序排列
+    /// 有分组（@group 或横幅注释）的文档块以分组名为标题；@class 声明的类与 owner 为该类的
+    /// 成员函数以类名为标题；其余的在文件导出了模块时归在模块标题下，
+    /// 存在类或模块时剩下的归在“其他”下（“其他”排在最后），否则不加标题
+    fn group_sections<'a>(&self, content: &[&'a DocBlock]) -> Vec<(Option<String>, Vec<&'a DocBlock>)> {
+        const OTHER: &str = "其他";
+        let classes: Vec<&str> = content.iter().filter_map(|b| b.class.as_deref()).collect();
+        let has_section = !classes.is_empty() || content.iter().any(|b| b.module.is_some());
+        let mut groups: Vec<(Option<String>, Vec<&DocBlock>)> = Vec::new();
+        for &block in content {
+            let class = block
+                .class
+                .as_deref()
+                .or_else(|| classes.iter().copied().find(|c| *c == block.owner_object));
+            let title = match (&block.group, class, &block.module) {
+                (Some(group), _, _) => Some(group.clone()),
+                (None, Some(class), _) => Some(class.to_string()),
+                (None, None, Some(module)) => Some(format!("模块 `{}`", module)),
+                (None, None, None) if has_section => Some(OTHER.to_string()),
+                (None, None, None) => None,
+            };
+            match groups.iter_mut().find(|(t, _)| *t == title) {
+                Some((_, blocks)) => blocks.push(block),
+                None => groups.push((title, vec![block])),
+            }
+        }
+        if let Some(pos) = groups.iter().position(|(t, _)| t.as_deref() == Some(OTHER)) {
+            let other = groups.remove(pos);
+            groups.push(other);
+        }
+        groups
+    }
+
+    /// 格式化 format 中的一个文档块（含 file_toc 模式下的标题与锚点）
+    fn format_grouped_block(&self, block: &DocBlock, anchors: &HashMap<String, String>) -> String {
         let mut s = String::new();
-        for block in content {
-            s.push_str(&self.format_block(&block));
+        if self.file_toc {
+            s.push_str(&self.heading(3, &block.function_name()));
+            // 别名也生成锚点，链接到别名时落在同一个函数上
+            for alias in &block.aliases {
+                s.push_str(&format!("<a id=\"{}\"></a>\n", anchor_slug(alias)));
+            }
+            if !block.aliases.is_empty() {
+                s.push('\n');
+            }
+        }
+        s.push_str(&self.format_block(block, anchors));
+        if self.style == Style::Full {
             s.push_str("---\n\n");
         }
+        s
+    }
+}
+
+impl OutputFileFormatter for MarkdownFormatter {
+    fn format_file(&self, source: &Path, content: &[DocBlock]) -> Result<String, String> {
+        let body = self.format(content)?;
+        if !self.front_matter {
+            return Ok(body);
+        }
+        Ok(FrontMatter::for_source(source).render() + &body)
+    }
+
+    fn format(&self, content: &[DocBlock]) -> Result<String, String> {
+        let mut s = String::new();
+        let visible: Vec<&DocBlock> = content.iter().filter(|b| self.include_private || !b.is_local).collect();
+        let groups = self.group_sections(&visible);
+        let ordered: Vec<&DocBlock> = groups.iter().flat_map(|(_, blocks)| blocks.iter().copied()).collect();
+        let anchors = unique_anchors(ordered.iter().copied());
+        // 只有输出函数标题时才有锚点可链接；同名函数链接到第一个
+        let mut links = HashMap::new();
+        if self.file_toc {
+            for (block, anchor) in ordered.iter().zip(&anchors) {
+                links.entry(block.function_name()).or_insert_with(|| anchor.clone());
+                for alias in &block.aliases {
+                    links.entry(alias.clone()).or_insert_with(|| anchor_slug(alias));
+                }
+            }
+        }
+        // 只有一个文档块时目录没有意义
+        if self.file_toc && visible.len() > 1 {
+            s.push_str(&self.format_toc(&ordered, &anchors));
+        }
+        for (title, blocks) in &groups {
+            if let Some(title) = title {
+                s.push_str(&self.heading(2, title));
+            }
+            for block in blocks {
+                s.push_str(&self.format_grouped_block(block, &links));
+            }
+        }
         Ok(s)
     }
 }
 
+/// 输出文件开头的 YAML front-matter，供 Hugo、Jekyll 等静态站点生成器读取
+///
+/// 字段按插入顺序输出，值一律写成双引号字符串。front-matter 只出现在文件的第一行，
+/// 而文档块之间的 `---` 分隔线前总有空行，两者不会混淆
+#[derive(Debug, Clone, Default)]
+pub struct FrontMatter {
+    fields: Vec<(String, String)>,
+}
+
+impl FrontMatter {
+    /// 以源文件名（不含扩展名）为 title、当前时间为 date
+    pub fn for_source(source: &Path) -> Self {
+        let title = source.file_stem().and_then(|s| s.to_str()).unwrap_or("API");
+        let mut front_matter = FrontMatter::default();
+        front_matter.insert("title", title);
+        front_matter.insert(
+            "date",
+            &chrono::Local::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, false),
+        );
+        front_matter
+    }
+
+    /// 设置一个字段，已存在时覆盖原值
+    pub fn insert(&mut self, key: &str, value: &str) {
+        match self.fields.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = value.to_string(),
+            None => self.fields.push((key.to_string(), value.to_string())),
+        }
+    }
+
+    pub fn render(&self) -> String {
+        let mut s = String::from("---\n");
+        for (key, value) in &self.fields {
+            let value = value.replace('\\', "\\\\").replace('"', "\\\"");
+            s.push_str(&format!("{}: \"{}\"\n", key, value));
+        }
+        s.push_str("---\n\n");
+        s
+    }
+}
+
+/// 检查 @includes 中引用的本地头文件是否存在
+/// 依次在源文件所在目录和 include_dirs 中查找，返回找不到的条目；`<...>` 系统头文件不做检查
+pub fn unresolved_includes(block: &DocBlock, source_dir: &Path, include_dirs: &[PathBuf]) -> Vec<String> {
+    let mut missing = Vec::new();
+    for inc in &block.includes {
+        let inc = inc.trim();
+        if inc.is_empty() || (inc.starts_with('<') && inc.ends_with('>')) {
+            continue;
+        }
+        let name = inc.trim_matches('"');
+        let found = std::iter::once(source_dir)
+            .chain(include_dirs.iter().map(PathBuf::as_path))
+            .any(|dir| dir.join(name).is_file());
+        if !found {
+            missing.push(inc.to_string());
+        }
+    }
+    missing
+}
+
+/// 把参数矩阵导出为 CSV：每个参数一行 (file, function, param, type, optional, description)，optional 为 true / false
+pub fn params_to_csv(files: &[FileDoc]) -> Result<String, csv::Error> {
+    let mut writer = csv::Writer::from_writer(vec![]);
+    writer.write_record(["file", "function", "param", "type", "optional", "description"])?;
+    for doc in files {
+        let file = doc.path.display().to_string();
+        for block in &doc.blocks {
+            let function = block.function_name();
+            for p in &block.parameters {
+                let optional = p.optional.to_string();
+                writer.write_record([&file, &function, &p.name, &p.type_name, &optional, &p.description])?;
+            }
+        }
+    }
+    let bytes = writer.into_inner().map_err(|e| e.into_error())?;
+    Ok(String::from_utf8_lossy(&bytes).into_owned())
+}
+
+/// 汇总所有文档块的 @todo，生成 Markdown 待办报告，每项注明所在文件与函数
+pub fn todo_report(files: &[FileDoc]) -> String {
+    let mut s = String::from("# TODO\n\n");
+    let mut count = 0;
+    for doc in files {
+        for block in &doc.blocks {
+            for todo in &block.todos {
+                s.push_str(&format!("- `{}` · `{}`: {}\n", doc.path.display(), block.function_name(), todo));
+                count += 1;
+            }
+        }
+    }
+    if count == 0 {
+        s.push_str("(无)\n");
+    }
+    s
+}
+
+/// DOT 中的带引号标识符，转义 `\` 与 `"`
+fn dot_id(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// 用所有文档块的 @includes 构建包含关系图，输出为 Graphviz DOT
+/// 节点是源文件与被包含的文件（`"x.h"` 去掉引号，`<x.h>` 保持原样），边从源文件指向被包含的文件；
+/// 节点与边按名字排序，输出稳定
+pub fn include_graph(files: &[FileDoc]) -> String {
+    let mut nodes = std::collections::BTreeSet::new();
+    let mut edges = std::collections::BTreeSet::new();
+    for doc in files {
+        let file = doc.path.display().to_string();
+        for block in &doc.blocks {
+            for inc in &block.includes {
+                let inc = inc.trim().trim_matches('"');
+                if inc.is_empty() {
+                    continue;
+                }
+                nodes.insert(file.clone());
+                nodes.insert(inc.to_string());
+                edges.insert((file.clone(), inc.to_string()));
+            }
+        }
+    }
+
+    let mut s = String::from("digraph includes {\n");
+    for node in &nodes {
+        s.push_str(&format!("    {};\n", dot_id(node)));
+    }
+    for (from, to) in &edges {
+        s.push_str(&format!("    {} -> {};\n", dot_id(from), dot_id(to)));
+    }
+    s.push_str("}\n");
+    s
+}
+
 /*
 Usage example:
 
 let parser = create_file_parser(InputFileType::Lua);
 let file = File::open("example.lua")?;
 let blocks = parser.parse(&file);
-let fmt = MarkdownFormatter;
-for b in &blocks {
-    let md = fmt.format(b);
-    // write md to file
-}
+let fmt = MarkdownFormatter::default();
+let md = fmt.format(&blocks)?;
+// write md to file
 
 */
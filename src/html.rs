@@ -0,0 +1,348 @@
+use crate::file_parser::{
+    unique_anchors, Description, DescriptionType, DocBlock, FormulaType, OutputFileFormatter, Parameter,
+};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::path::Path;
+
+/// 生成页面内嵌的最小样式表
+const STYLESHEET: &str = "\
+body { font-family: system-ui, sans-serif; max-width: 52rem; margin: 2rem auto; padding: 0 1rem; line-height: 1.6; color: #222; }
+pre { background: #f5f5f5; padding: 0.75rem; overflow-x: auto; border-radius: 4px; }
+code { font-family: ui-monospace, monospace; }
+section { border-bottom: 1px solid #ddd; padding-bottom: 1rem; }
+.badges, .meta { color: #666; font-size: 0.9em; }
+.deprecated, .todo, .note { border-left: 4px solid #e0a800; padding-left: 0.75rem; margin-left: 0; }
+";
+
+/// 有公式时加载 MathJax，渲染 `\(...\)` 与 `\[...\]`
+const MATHJAX: &str = "<script defer src=\"https://cdn.jsdelivr.net/npm/mathjax@3/es5/tex-chtml.js\"></script>\n";
+
+/// HTML 格式化器：把一个文件的文档块生成为一个完整的独立 HTML 页面
+///
+/// 签名与示例放在 `<pre><code>` 中，参数等列表用 `<ul>`，公式使用 MathJax 的
+/// `\(...\)`（行内）与 `\[...\]`（块级）定界符。所有来自源码的文本都经过转义
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HtmlFormatter {
+    /// 是否输出私有的文档块（is_local），默认省略
+    pub include_private: bool,
+}
+
+/// 转义 HTML 特殊字符，代码中的 `<`、`>` 按字面显示
+pub fn escape_html(text: &str) -> String {
+    let mut s = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => s.push_str("&amp;"),
+            '<' => s.push_str("&lt;"),
+            '>' => s.push_str("&gt;"),
+            '"' => s.push_str("&quot;"),
+            '\'' => s.push_str("&#39;"),
+            c => s.push(c),
+        }
+    }
+    s
+}
+
+impl HtmlFormatter {
+    /// 生成完整的页面，title 为页面标题
+    fn page(&self, title: &str, content: &[DocBlock]) -> String {
+        let content: Vec<&DocBlock> = content.iter().filter(|b| self.include_private || !b.is_local).collect();
+        let anchors = unique_anchors(content.iter().copied());
+        let links: HashMap<String, String> = content
+            .iter()
+            .zip(&anchors)
+            .rev()
+            .map(|(block, anchor)| (block.function_name(), anchor.clone()))
+            .collect();
+        let has_formula = content.iter().any(|b| {
+            b.descriptions.iter().any(|d| matches!(d.dtype, DescriptionType::MathFormula(..)))
+        });
+
+        let mut s = String::from("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        let _ = writeln!(s, "<title>{}</title>", escape_html(title));
+        let _ = write!(s, "<style>\n{}</style>\n", STYLESHEET);
+        if has_formula {
+            s.push_str(MATHJAX);
+        }
+        s.push_str("</head>\n<body>\n");
+        let _ = writeln!(s, "<h1>{}</h1>", escape_html(title));
+        if content.len() > 1 {
+            s.push_str("<nav>\n<h2>Contents</h2>\n<ul>\n");
+            for (block, anchor) in content.iter().zip(&anchors) {
+                let _ = writeln!(s, "<li><a href=\"#{}\">{}</a></li>", anchor, escape_html(&block.function_name()));
+            }
+            s.push_str("</ul>\n</nav>\n");
+        }
+        for (block, anchor) in content.iter().zip(&anchors) {
+            s.push_str(&self.format_block(block, anchor, &links));
+        }
+        s.push_str("</body>\n</html>\n");
+        s
+    }
+
+    /// 格式化单个文档块为一个 `<section>`，anchor 为其 id
+    fn format_block(&self, block: &DocBlock, anchor: &str, links: &HashMap<String, String>) -> String {
+        let mut s = format!("<section id=\"{}\">\n", anchor);
+        let _ = writeln!(s, "<h3>{}</h3>", escape_html(&block.function_name()));
+        match block.deprecated.as_deref() {
+            None => {}
+            Some("") => s.push_str("<p class=\"deprecated\"><strong>Deprecated</strong></p>\n"),
+            Some(message) => {
+                let _ = writeln!(s, "<p class=\"deprecated\"><strong>Deprecated:</strong> {}</p>", escape_html(message));
+            }
+        }
+        s.push_str(&code_block(block.lang.fence_name(), &block.signature.raw));
+        s.push_str(&badges(block));
+        if !block.includes.is_empty() {
+            let _ = writeln!(s, "<p><strong>Includes:</strong> {}</p>", escape_html(&block.includes.join(", ")));
+        }
+        if !block.brief.is_empty() {
+            let _ = writeln!(s, "<p><strong>Brief:</strong> {}</p>", escape_html(&block.brief));
+        }
+        if !block.aliases.is_empty() {
+            let _ = writeln!(s, "<p><strong>Aliases:</strong> {}</p>", code_list(&block.aliases));
+        }
+        if !block.generics.is_empty() {
+            s.push_str(&item_list("Type parameters", block.generics.iter().map(|g| inline_code(g))));
+        }
+        if let Some(receiver) = &block.receiver {
+            let _ = writeln!(s, "<p><strong>Receiver:</strong> {}</p>", parameter(receiver));
+        }
+        s.push_str(&parameter_list("Parameters", &block.parameters));
+        s.push_str(&return_list(&block.ret_value));
+        s.push_str(&parameter_list("Yields", &block.yields));
+        s.push_str(&parameter_list("Errors", &block.errors));
+        s.push_str(&parameter_list("Properties", &block.properties));
+        s.push_str(&parameter_list("Fields", &block.fields));
+        if !block.descriptions.is_empty() {
+            s.push_str("<h4>Description</h4>\n");
+            s.push_str(&descriptions(&block.descriptions));
+        }
+        if let Some(code) = &block.example {
+            s.push_str("<h4>Example</h4>\n");
+            s.push_str(&code_block(block.lang.fence_name(), code.trim_end()));
+        }
+        if !block.see.is_empty() {
+            let items: Vec<String> = block
+                .see
+                .iter()
+                .map(|name| match links.get(name) {
+                    Some(anchor) => format!("<a href=\"#{}\">{}</a>", anchor, inline_code(name)),
+                    None => inline_code(name),
+                })
+                .collect();
+            let _ = writeln!(s, "<p><strong>See also:</strong> {}</p>", items.join(", "));
+        }
+        for todo in &block.todos {
+            let _ = writeln!(s, "<p class=\"todo\"><strong>TODO:</strong> {}</p>", escape_html(todo));
+        }
+        s.push_str(&metadata(block));
+        s.push_str("</section>\n");
+        s
+    }
+}
+
+impl OutputFileFormatter for HtmlFormatter {
+    fn format_file(&self, source: &Path, content: &[DocBlock]) -> Result<String, String> {
+        let title = source.file_name().and_then(|s| s.to_str()).unwrap_or("API");
+        Ok(self.page(title, content))
+    }
+
+    fn format(&self, content: &[DocBlock]) -> Result<String, String> {
+        Ok(self.page("API", content))
+    }
+}
+
+fn inline_code(text: &str) -> String {
+    format!("<code>{}</code>", escape_html(text))
+}
+
+fn code_list(items: &[String]) -> String {
+    items.iter().map(|i| inline_code(i)).collect::<Vec<_>>().join(", ")
+}
+
+/// `<pre><code>` 代码块，lang 为空时不加 class
+fn code_block(lang: &str, code: &str) -> String {
+    match lang {
+        "" => format!("<pre><code>{}</code></pre>\n", escape_html(code)),
+        lang => format!("<pre><code class=\"language-{}\">{}</code></pre>\n", lang, escape_html(code)),
+    }
+}
+
+/// 签名下方的元信息行 (since/async/...)
+fn badges(block: &DocBlock) -> String {
+    let mut badges = Vec::new();
+    if let Some(since) = &block.since {
+        badges.push(format!("since {}", escape_html(since)));
+    }
+    if block.is_async {
+        badges.push("async".to_string());
+    }
+    if block.deprecated.is_some() {
+        badges.push("deprecated".to_string());
+    }
+    if let Some(complexity) = &block.complexity {
+        badges.push(format!("complexity {}", escape_html(complexity)));
+    }
+    if block.is_local {
+        badges.push("(local)".to_string());
+    }
+    if badges.is_empty() {
+        return String::new();
+    }
+    format!("<p class=\"badges\">{}</p>\n", badges.join(" · "))
+}
+
+/// 作者与版本组成的页脚
+fn metadata(block: &DocBlock) -> String {
+    let mut items = Vec::new();
+    if let Some(author) = &block.author {
+        items.push(format!("Author: {}", escape_html(author)));
+    }
+    if let Some(version) = &block.version {
+        items.push(format!("Version: {}", escape_html(version)));
+    }
+    if items.is_empty() {
+        return String::new();
+    }
+    format!("<p class=\"meta\">{}</p>\n", items.join(" · "))
+}
+
+/// 带标题的 `<ul>` 列表，items 已经是 HTML
+fn item_list(title: &str, items: impl Iterator<Item = String>) -> String {
+    let mut s = format!("<p><strong>{}:</strong></p>\n<ul>\n", title);
+    for item in items {
+        let _ = writeln!(s, "<li>{}</li>", item);
+    }
+    s.push_str("</ul>\n");
+    s
+}
+
+/// 单个参数：名字、类型（可选、方向）与描述，子标签内容接在描述之后
+fn parameter(p: &Parameter) -> String {
+    let mut s = match p.name.as_str() {
+        "" => String::new(),
+        name => inline_code(name),
+    };
+    let mut attrs = Vec::new();
+    if !p.type_name.is_empty() {
+        attrs.push(format!("<em>{}</em>", escape_html(&p.type_name)));
+    }
+    if p.optional {
+        attrs.push("optional".to_string());
+    }
+    if let Some(direction) = &p.direction {
+        attrs.push(escape_html(direction));
+    }
+    if p.is_variadic() {
+        attrs.push("variadic".to_string());
+    }
+    if !attrs.is_empty() {
+        if !s.is_empty() {
+            s.push(' ');
+        }
+        let _ = write!(s, "({})", attrs.join(", "));
+    }
+    if !p.description.is_empty() {
+        let _ = write!(s, ": {}", escape_html(&p.description));
+    }
+    if !p.details.is_empty() {
+        s.push('\n');
+        s.push_str(&descriptions(&p.details));
+    }
+    s
+}
+
+fn parameter_list(title: &str, params: &[Parameter]) -> String {
+    if params.is_empty() {
+        return String::new();
+    }
+    item_list(title, params.iter().map(parameter))
+}
+
+/// 返回值列表：多个返回值时按 number 标为 Return 1、Return 2……
+fn return_list(rets: &[Parameter]) -> String {
+    match rets {
+        [] => String::new(),
+        [_] => parameter_list("Returns", rets),
+        _ => item_list("Returns", rets.iter().map(|p| format!("Return {} {}", p.number + 1, parameter(p)))),
+    }
+}
+
+/// 描述项：相邻的文字合并为一个段落（空文字项是段落分隔），列表按层级嵌套
+fn descriptions(items: &[Description]) -> String {
+    let mut s = String::new();
+    let mut paragraph: Vec<&str> = Vec::new();
+    // 当前打开的 <ul> 层数
+    let mut depth = 0;
+    for item in items {
+        if !matches!(item.dtype, DescriptionType::Text(_)) || item.content.is_empty() {
+            flush_paragraph(&mut s, &mut paragraph);
+        }
+        if !matches!(item.dtype, DescriptionType::BulletList(..)) {
+            close_lists(&mut s, &mut depth);
+        }
+        match &item.dtype {
+            DescriptionType::Text(_) if item.content.is_empty() => {}
+            DescriptionType::Text(_) => paragraph.push(&item.content),
+            DescriptionType::Code(lang, _) => s.push_str(&code_block(lang.fence_name(), &item.content)),
+            DescriptionType::MathFormula(FormulaType::Inline, _) => {
+                let _ = writeln!(s, "<p>\\({}\\)</p>", escape_html(&item.content));
+            }
+            DescriptionType::MathFormula(FormulaType::Block, _) => {
+                let _ = writeln!(s, "<div>\\[{}\\]</div>", escape_html(&item.content));
+            }
+            DescriptionType::BulletList(level, _) => {
+                let target = (*level).max(0) as usize + 1;
+                if target > depth {
+                    while depth < target {
+                        s.push_str("<ul>\n");
+                        depth += 1;
+                    }
+                } else {
+                    s.push_str("</li>\n");
+                    while depth > target {
+                        s.push_str("</ul>\n</li>\n");
+                        depth -= 1;
+                    }
+                }
+                let content = item.content.trim();
+                let content = content.strip_prefix('-').map_or(content, str::trim_start);
+                let _ = write!(s, "<li>{}", escape_html(content));
+            }
+            DescriptionType::HTMLLink(_) => {
+                let url = escape_html(&item.content);
+                let _ = writeln!(s, "<p><a href=\"{}\">{}</a></p>", url, url);
+            }
+            DescriptionType::Note(_) => {
+                let _ = writeln!(s, "<blockquote class=\"note\"><strong>Note:</strong> {}</blockquote>", escape_html(&item.content));
+            }
+        }
+    }
+    flush_paragraph(&mut s, &mut paragraph);
+    close_lists(&mut s, &mut depth);
+    s
+}
+
+fn flush_paragraph(s: &mut String, paragraph: &mut Vec<&str>) {
+    if paragraph.is_empty() {
+        return;
+    }
+    let _ = writeln!(s, "<p>{}</p>", escape_html(&paragraph.join("\n")));
+    paragraph.clear();
+}
+
+fn close_lists(s: &mut String, depth: &mut usize) {
+    if *depth == 0 {
+        return;
+    }
+    s.push_str("</li>\n");
+    while *depth > 0 {
+        s.push_str("</ul>\n");
+        *depth -= 1;
+        if *depth > 0 {
+            s.push_str("</li>\n");
+        }
+    }
+}
@@ -0,0 +1,20 @@
+use crate::file_parser::{DocBlock, OutputFileFormatter};
+
+/// JSON 格式化器：把一个文件的所有文档块序列化为 JSON 数组，供其他工具读取
+///
+/// 每个元素即 DocBlock 的全部字段（signature、parameters、ret_value、descriptions、
+/// includes 以及 is_local 等布尔标记），字段名与结构体一致
+#[derive(Debug, Clone, Copy, Default)]
+pub struct JsonFormatter {
+    /// 是否输出私有的文档块（is_local），默认省略
+    pub include_private: bool,
+}
+
+impl OutputFileFormatter for JsonFormatter {
+    fn format(&self, content: &[DocBlock]) -> Result<String, String> {
+        let content: Vec<&DocBlock> = content.iter().filter(|b| self.include_private || !b.is_local).collect();
+        let mut s = serde_json::to_string_pretty(&content).map_err(|e| e.to_string())?;
+        s.push('\n');
+        Ok(s)
+    }
+}
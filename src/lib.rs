@@ -0,0 +1,349 @@
+//! 从源码文档注释生成 Markdown 文档
+//!
+//! 最简单的用法是 parse_file 解析单个文件、render_markdown 渲染结果；
+//! 需要更多控制时使用 document_file / document_tree 与 file_parser 中的格式化器
+//!
+//! ```no_run
+//! let blocks = todocument::parse_file(std::path::Path::new("init.lua"))?;
+//! print!("{}", todocument::render_markdown(&blocks));
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+pub mod diagnostic;
+pub mod file_parser;
+pub mod html;
+pub mod json;
+pub mod template;
+pub mod watch;
+
+pub use file_parser::{create_file_parser, DocBlock, InputFileType, MarkdownFormatter, OutputFileFormatter};
+use file_parser::name_anonymous_blocks;
+use diagnostic::{Diagnostic, Level};
+use globset::{Glob, GlobMatcher, GlobSet, GlobSetBuilder};
+use ignore::WalkBuilder;
+use rayon::prelude::*;
+use std::fmt;
+use std::fs::File;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// 输出后处理钩子：接收格式化完成的完整文本，返回最终写出的文本
+pub type PostProcess<'a> = &'a mut dyn FnMut(String) -> String;
+
+/// 把从 source 解析得到的文档块生成为最终输出文本
+///
+/// 处理顺序：
+/// 1. 由 formatter 的 format_file 格式化所有文档块（front-matter 等依赖源文件的文档级内容也在这一步插入）
+/// 2. 若提供了 post_process，则在格式化结果的基础上调用一次，其返回值即为最终输出
+///
+/// 因此钩子看到的是除写文件以外已经完整的文档，可以安全地插入目录占位符、改写链接等；
+/// 命令行的渲染步骤也经过这里
+pub fn generate(
+    source: &Path,
+    blocks: &[DocBlock],
+    formatter: &dyn OutputFileFormatter,
+    post_process: Option<PostProcess>,
+) -> Result<String, String> {
+    let content = formatter.format_file(source, blocks)?;
+    Ok(match post_process {
+        Some(hook) => hook(content),
+        None => content,
+    })
+}
+
+/// 库接口的错误类型
+#[derive(Debug)]
+pub enum ToDocError {
+    Io(PathBuf, io::Error),
+    Pattern(globset::Error),
+    UnsupportedLanguage(PathBuf),
+    /// 无法解析的语言覆盖规则（应为 `GLOB=LANG`）
+    LanguageRule(String),
+    /// 格式化输出失败
+    Render(PathBuf, String),
+    /// 解析或生成过程中发生 panic
+    Panic(PathBuf, String),
+    Watch(notify::Error),
+}
+
+impl fmt::Display for ToDocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ToDocError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            ToDocError::Pattern(e) => write!(f, "无效的路径模式: {}", e),
+            ToDocError::UnsupportedLanguage(path) => write!(f, "不支持的文件类型: {}", path.display()),
+            ToDocError::LanguageRule(rule) => write!(f, "无效的语言覆盖规则: {} (应为 GLOB=LANG)", rule),
+            ToDocError::Render(path, e) => write!(f, "{}: 格式化失败: {}", path.display(), e),
+            ToDocError::Panic(path, e) => write!(f, "{}: 处理时发生内部错误: {}", path.display(), e),
+            ToDocError::Watch(e) => write!(f, "文件监视失败: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ToDocError {}
+
+impl ToDocError {
+    /// 机器可读的错误标识，用于诊断输出
+    pub fn code(&self) -> &'static str {
+        match self {
+            ToDocError::Io(..) => "io",
+            ToDocError::Pattern(_) => "invalid-pattern",
+            ToDocError::UnsupportedLanguage(_) => "unsupported-language",
+            ToDocError::LanguageRule(_) => "invalid-language-rule",
+            ToDocError::Render(..) => "render",
+            ToDocError::Panic(..) => "panic",
+            ToDocError::Watch(_) => "watch",
+        }
+    }
+}
+
+impl From<globset::Error> for ToDocError {
+    fn from(e: globset::Error) -> Self {
+        ToDocError::Pattern(e)
+    }
+}
+
+impl From<notify::Error> for ToDocError {
+    fn from(e: notify::Error) -> Self {
+        ToDocError::Watch(e)
+    }
+}
+
+/// 扫描目录时的选项
+#[derive(Debug, Clone, Default)]
+pub struct Options {
+    /// 是否递归处理子目录
+    pub recursive: bool,
+    /// 递归时最多进入的子目录层数，扫描根目录为第 0 层；None 表示不限制
+    pub max_depth: Option<usize>,
+    /// 只处理匹配这些模式的文件（相对扫描根目录），为空表示不限制
+    pub only: Vec<String>,
+    /// 跳过匹配这些模式的文件（相对扫描根目录），优先于 `only`
+    pub exclude: Vec<String>,
+    /// 按路径覆盖语言识别的规则，格式为 `GLOB=LANG`，见 LanguageOverrides
+    pub lang_for: Vec<String>,
+    /// 不遵循 .gitignore / .ignore，也不跳过隐藏文件与目录
+    pub no_ignore: bool,
+}
+
+impl Options {
+    /// root 下的 path 是否会被 document_tree 选中（语言可识别且满足过滤规则）
+    pub fn selects(&self, root: &Path, path: &Path) -> Result<bool, ToDocError> {
+        Ok(ScanFilter::new(root, self)?.select(path).is_some())
+    }
+}
+
+/// 按路径模式覆盖语言识别的规则
+///
+/// 每条规则形如 `*.lua.in=lua`，模式与完整路径匹配（`*` 可跨越目录分隔符）。
+/// 按给出的顺序匹配，第一条匹配的规则生效；没有规则匹配时按扩展名识别
+#[derive(Debug, Clone, Default)]
+pub struct LanguageOverrides {
+    rules: Vec<(GlobMatcher, InputFileType)>,
+}
+
+impl LanguageOverrides {
+    pub fn new(rules: &[String]) -> Result<Self, ToDocError> {
+        let mut parsed = Vec::new();
+        for rule in rules {
+            let (pattern, lang) = rule
+                .rsplit_once('=')
+                .ok_or_else(|| ToDocError::LanguageRule(rule.clone()))?;
+            let lang = InputFileType::from_str(lang.trim())
+                .ok_or_else(|| ToDocError::LanguageRule(rule.clone()))?;
+            parsed.push((Glob::new(pattern.trim())?.compile_matcher(), lang));
+        }
+        Ok(LanguageOverrides { rules: parsed })
+    }
+
+    /// 识别 path 的语言：先查覆盖规则，再按扩展名推断
+    pub fn language_of(&self, path: &Path) -> Option<InputFileType> {
+        self.rules
+            .iter()
+            .find(|(glob, _)| glob.is_match(path))
+            .map(|(_, lang)| *lang)
+            .or_else(|| InputFileType::from_path(path))
+    }
+}
+
+/// 单个源文件的解析结果
+#[derive(Debug)]
+pub struct FileDoc {
+    pub path: PathBuf,
+    pub lang: InputFileType,
+    pub blocks: Vec<DocBlock>,
+}
+
+/// 目录扫描时的路径过滤规则，路径相对于扫描根目录匹配
+struct ScanFilter {
+    root: PathBuf,
+    only: Option<GlobSet>,
+    exclude: Option<GlobSet>,
+    langs: LanguageOverrides,
+}
+
+/// 把一组模式编译为 GlobSet，没有模式时返回 None
+fn glob_set(patterns: &[String]) -> Result<Option<GlobSet>, ToDocError> {
+    if patterns.is_empty() {
+        return Ok(None);
+    }
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern)?);
+    }
+    Ok(Some(builder.build()?))
+}
+
+impl ScanFilter {
+    fn new(root: &Path, opts: &Options) -> Result<Self, ToDocError> {
+        let only = glob_set(&opts.only)?;
+        let exclude = glob_set(&opts.exclude)?;
+        let langs = LanguageOverrides::new(&opts.lang_for)?;
+        Ok(ScanFilter { root: root.to_path_buf(), only, exclude, langs })
+    }
+
+    /// 文件被选中时返回其语言
+    fn select(&self, path: &Path) -> Option<InputFileType> {
+        let rel = path.strip_prefix(&self.root).unwrap_or(path);
+        if self.exclude.as_ref().is_some_and(|set| set.is_match(rel)) {
+            return None;
+        }
+        if !self.only.as_ref().is_none_or(|set| set.is_match(rel)) {
+            return None;
+        }
+        self.langs.language_of(path)
+    }
+}
+
+/// 识别语言（覆盖规则优先，其次扩展名）并解析单个源文件
+pub fn document_file(path: &Path, langs: &LanguageOverrides) -> Result<FileDoc, ToDocError> {
+    let lang = langs
+        .language_of(path)
+        .ok_or_else(|| ToDocError::UnsupportedLanguage(path.to_path_buf()))?;
+    document_file_as(path, lang)
+}
+
+/// 按扩展名识别语言并解析单个源文件，返回其中的文档块
+///
+/// 不支持的文件类型返回 ErrorKind::InvalidInput
+pub fn parse_file(path: &Path) -> io::Result<Vec<DocBlock>> {
+    let lang = InputFileType::from_path(path).ok_or_else(|| {
+        io::Error::new(io::ErrorKind::InvalidInput, ToDocError::UnsupportedLanguage(path.to_path_buf()))
+    })?;
+    match document_file_as(path, lang) {
+        Ok(doc) => Ok(doc.blocks),
+        Err(ToDocError::Io(_, e)) => Err(e),
+        Err(e) => Err(io::Error::other(e)),
+    }
+}
+
+/// 用默认设置的 MarkdownFormatter 渲染文档块
+pub fn render_markdown(blocks: &[DocBlock]) -> String {
+    MarkdownFormatter::default().format(blocks).expect("Markdown 格式化不会失败")
+}
+
+/// 按指定语言解析单个源文件
+fn document_file_as(path: &Path, lang: InputFileType) -> Result<FileDoc, ToDocError> {
+    let file = File::open(path).map_err(|e| ToDocError::Io(path.to_path_buf(), e))?;
+    let parser = create_file_parser(&Some(lang));
+    let mut blocks = parser.parse(&file);
+    if let Some(module_name) = path.file_stem().and_then(|s| s.to_str()) {
+        name_anonymous_blocks(&mut blocks, module_name);
+    }
+    Ok(FileDoc { path: path.to_path_buf(), lang, blocks })
+}
+
+/// 为带 `@example-file` 的文档块读取外部示例文件
+///
+/// 示例文件在 examples_dir 中查找，未指定时使用源文件旁的 `examples/` 目录；
+/// 先按原名查找，找不到再尝试追加源文件的扩展名（`add` -> `add.lua`）。
+/// 返回找不到的示例文件名
+pub fn load_examples(doc: &mut FileDoc, examples_dir: Option<&Path>) -> Vec<String> {
+    let default_dir = doc.path.parent().unwrap_or(Path::new(".")).join("examples");
+    let dir = examples_dir.unwrap_or(&default_dir);
+    let ext = doc.path.extension().and_then(|e| e.to_str()).unwrap_or("");
+    let mut missing = Vec::new();
+    for block in doc.blocks.iter_mut() {
+        let name = match &block.example_file {
+            Some(name) => name.clone(),
+            None => continue,
+        };
+        let candidates = [dir.join(&name), dir.join(format!("{}.{}", name, ext))];
+        match candidates.iter().find_map(|p| std::fs::read_to_string(p).ok()) {
+            Some(code) => block.example = Some(code),
+            None => missing.push(name),
+        }
+    }
+    missing
+}
+
+/// document_tree 的扫描结果
+#[derive(Debug, Default)]
+pub struct DocTree {
+    /// 解析得到的文件，按路径排序
+    pub docs: Vec<FileDoc>,
+    /// 无法读取的文件及其错误，按路径排序；单个文件出错不影响其余文件
+    pub errors: Vec<(PathBuf, ToDocError)>,
+    /// 扫描过程中的警告（如跳过的目录），不影响其余文件
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+/// 扫描目录并解析其中所有支持的源文件
+///
+/// 先收集所有选中的文件，再在 rayon 线程池中并行解析；返回结果按路径排序，
+/// 同样的输入每次得到同样的顺序。只有根目录本身无法扫描时返回错误，
+/// 单个文件的错误记入 DocTree::errors
+pub fn document_tree(root: &Path, opts: &Options) -> Result<DocTree, ToDocError> {
+    let filter = ScanFilter::new(root, opts)?;
+    let mut tree = DocTree::default();
+    let files = walk_directory(root, opts, &filter, &mut tree.diagnostics)?;
+    let results: Vec<_> = files.par_iter().map(|(path, lang)| document_file_as(path, *lang)).collect();
+    for ((path, _), result) in files.into_iter().zip(results) {
+        match result {
+            Ok(doc) => tree.docs.push(doc),
+            Err(e) => tree.errors.push((path, e)),
+        }
+    }
+    Ok(tree)
+}
+
+/// 收集 root 下被选中的文件
+///
+/// 默认遵循 .gitignore / .ignore 并跳过隐藏文件（如 `.git/`），`no_ignore` 时全部扫描；
+/// 超过 `max_depth` 的子目录直接不再进入。符号链接会被跟随，链接成环时跳过该目录，警告记入 diagnostics。
+/// 文件系统返回条目的顺序不确定，结果按路径排序
+fn walk_directory(
+    root: &Path,
+    opts: &Options,
+    filter: &ScanFilter,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Result<Vec<(PathBuf, InputFileType)>, ToDocError> {
+    std::fs::read_dir(root).map_err(|e| ToDocError::Io(root.to_path_buf(), e))?;
+    let walker = WalkBuilder::new(root)
+        .standard_filters(!opts.no_ignore)
+        .require_git(false)
+        .follow_links(true)
+        // WalkBuilder 中根目录下的条目深度为 1
+        .max_depth(if opts.recursive { opts.max_depth.map(|n| n + 1) } else { Some(1) })
+        .build();
+
+    let mut files = Vec::new();
+    for entry in walker {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                diagnostics.push(Diagnostic::global(Level::Warning, "scan-skipped", format!("扫描目录时跳过: {}", e)));
+                continue;
+            }
+        };
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        // 只处理能识别语言的源码文件
+        if let Some(lang) = filter.select(entry.path()) {
+            files.push((entry.into_path(), lang));
+        }
+    }
+    files.sort_by(|(a, _), (b, _)| a.cmp(b));
+    Ok(files)
+}
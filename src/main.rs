@@ -1,12 +1,20 @@
-mod file_parser;
 use clap::Parser;
-use file_parser::{create_file_parser, InputFileType, MarkdownFormatter};
+use todocument::{document_file, document_tree, generate, load_examples, FileDoc, LanguageOverrides, Options, ToDocError};
+use todocument::diagnostic::{Diagnostic, Level};
+use todocument::html::HtmlFormatter;
+use todocument::json::JsonFormatter;
+use todocument::template::TemplateFormatter;
+use todocument::watch::watch;
+use todocument::file_parser::{include_graph, params_to_csv, todo_report, unresolved_includes, DocBlock, MarkdownFormatter, OutputFileFormatter, ParamStyle, Style};
 use once_cell::sync::Lazy;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
 use std::env;
-use std::fs::File;
+use std::fs::OpenOptions;
 use std::io::Write;
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
 
 /*
  * todoc --files code.lua
@@ -23,146 +31,804 @@ pub static WORKSPACE: Lazy<Mutex<String>> = Lazy::new(|| {
 #[derive(Parser, Debug)]
 #[command(author = "LiZhuoran", version = "0.1", about = "Doc Generator", long_about = None)]
 pub struct Args {
-    #[arg(long, num_args = 1.., help = "指定要处理的文件路径")]
+    #[arg(long, num_args = 1.., help = "指定要处理的文件路径，支持通配符模式（如 \"src/**/*.lua\"）")]
     pub files: Vec<String>,
 
+    #[arg(long, value_name = "LIST", help = "从 LIST 读取要处理的文件（每行一个路径，忽略空行与 # 注释），相对路径相对于 LIST 所在目录；与 --files 的文件合并")]
+    pub files_from: Option<PathBuf>,
+
     #[arg(short, long, help = "处理当前目录下的所有文件")]
     pub all: bool,
 
     #[arg(short, long, help = "是否递归处理子目录")]
     pub recursive: bool,
+
+    #[arg(long, value_name = "N", requires = "recursive", help = "递归时最多进入 N 层子目录，当前目录为第 0 层")]
+    pub max_depth: Option<usize>,
+
+    #[arg(long, help = "所有文件均未产生文档块时以非零状态码退出")]
+    pub require_docs: bool,
+
+    #[arg(long, help = "出现任何警告或错误时以非零状态码退出")]
+    pub fail_on_warning: bool,
+
+    #[arg(long, value_name = "FILE", help = "把所有函数的参数导出为 CSV 文件")]
+    pub params_csv: Option<String>,
+
+    #[arg(long, value_name = "FILE", help = "把 @includes 描述的包含关系导出为 Graphviz DOT 文件")]
+    pub include_graph: Option<PathBuf>,
+
+    #[arg(long, value_name = "FILE", help = "把所有 @todo 汇总为一个 Markdown 待办报告")]
+    pub todo_report: Option<PathBuf>,
+
+    #[arg(long = "include-dirs", value_name = "DIR", help = "检查 @includes 引用的头文件时额外搜索的目录，可重复指定")]
+    pub include_dirs: Vec<PathBuf>,
+
+    #[arg(long, value_name = "GLOB", help = "扫描目录时只处理匹配的文件（相对扫描根目录），可重复指定")]
+    pub only: Vec<String>,
+
+    #[arg(long, value_name = "GLOB", help = "扫描目录时跳过匹配的文件（相对扫描根目录，如 \"**/test/**\"），可重复指定")]
+    pub exclude: Vec<String>,
+
+    #[arg(long, help = "扫描目录时不遵循 .gitignore，也不跳过隐藏文件与目录")]
+    pub no_ignore: bool,
+
+    #[arg(long, help = "不在签名下方输出 since/async 等元信息徽章")]
+    pub no_badges: bool,
+
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t = OutputFormat::Markdown, help = "输出格式，决定生成文件的扩展名")]
+    pub format: OutputFormat,
+
+    #[arg(long, value_enum, value_name = "STYLE", default_value_t = Style::Full, help = "文档块的渲染风格，compact 为签名加一行参数摘要的速查表")]
+    pub style: Style,
+
+    #[arg(long, value_enum, value_name = "STYLE", default_value_t = ParamStyle::List, help = "参数列表的渲染方式，table 为 Name/Type/Description 表格")]
+    pub param_style: ParamStyle,
+
+    #[arg(long, help = "不输出签名代码块，改为以函数名作为标题")]
+    pub no_signature: bool,
+
+    #[arg(long, help = "输出私有函数（Lua 的 local 函数、C 的 static 函数等），并以 (local) 徽章标出；默认省略")]
+    pub include_private: bool,
+
+    #[arg(long, help = "把 `:` 定义的方法隐含的 self 作为第一个参数列出（类型为所属对象），默认单独输出一行 Receiver")]
+    pub self_param: bool,
+
+    #[arg(long, conflicts_with = "merge", help = "在生成的 Markdown 开头加上 YAML front-matter（源文件名作为 title 及生成时间），供静态站点生成器使用")]
+    pub front_matter: bool,
+
+    #[arg(long, help = "信任注释中的 Markdown：不转义简介与描述中的 * _ ` [ ] < 等字符")]
+    pub raw_markdown: bool,
+
+    #[arg(long, value_name = "DIR", help = "使用目录中的 tera 模板 (block.md.tera / index.md.tera) 渲染输出")]
+    pub template_dir: Option<PathBuf>,
+
+    #[arg(long, value_enum, value_name = "ORDER", help = "参数的排列顺序，默认按 @param 的书写顺序（Lua 函数总是按签名顺序）")]
+    pub sort_params: Option<ParamOrder>,
+
+    #[arg(long, help = "不输出文件开头的函数目录与每个函数的标题")]
+    pub no_toc: bool,
+
+    /// 目录现在默认输出，保留该选项只为兼容旧的命令行
+    #[arg(long, hide = true)]
+    pub file_toc: bool,
+
+    #[arg(long, value_name = "DIR", help = "@example-file 引用的示例文件所在目录，默认为源文件旁的 examples/")]
+    pub examples_dir: Option<PathBuf>,
+
+    #[arg(long, value_name = "N", default_value_t = 0, help = "所有生成的标题级别下移 N 级（最多到 H6）")]
+    pub heading_offset: usize,
+
+    #[arg(long = "lang-for", value_name = "GLOB=LANG", help = "路径匹配 GLOB 的文件按 LANG 解析（如 \"*.lua.in=lua\"），优先于扩展名识别，可重复指定")]
+    pub lang_for: Vec<String>,
+
+    #[arg(short, long, value_name = "N", help = "并行处理目录中文件的最大线程数，默认为 CPU 核数")]
+    pub jobs: Option<usize>,
+
+    #[arg(short, long, action = clap::ArgAction::Count, help = "输出处理进度，重复两次 (-vv) 输出逐行的调试信息")]
+    pub verbose: u8,
+
+    #[arg(short, long, conflicts_with = "verbose", help = "只输出错误，不输出警告")]
+    pub quiet: bool,
+
+    #[arg(long, value_enum, value_name = "FORMAT", default_value_t = LogFormat::Text, help = "警告与错误在 stderr 上的输出格式")]
+    pub log_format: LogFormat,
+
+    #[arg(long, conflicts_with_all = ["merge", "output_dir"], help = "把生成的文档输出到标准输出而不是写文件；多个文件时以注释行分隔")]
+    pub stdout: bool,
+
+    #[arg(long, value_name = "DIR", conflicts_with = "merge", help = "把生成的 .md 写入 DIR 而不是源文件旁；与 --recursive 同用时保留相对目录结构")]
+    pub output_dir: Option<PathBuf>,
+
+    #[arg(short, long, value_name = "FILE", conflicts_with_all = ["merge", "output_dir", "stdout", "all"], help = "把生成的文档写入 FILE（不改动扩展名），只能用于单个输入文件")]
+    pub output: Option<PathBuf>,
+
+    #[arg(long, visible_alias = "combine", value_name = "FILE", help = "把所有文件的文档合并写入 FILE（按源文件路径排序），而不是为每个源文件生成一个 .md")]
+    pub merge: Option<PathBuf>,
+
+    #[arg(long, requires = "merge", help = "追加到 --merge 指定的已有文件末尾，而不是覆盖它")]
+    pub append: bool,
+
+    #[arg(long, requires = "merge", help = "--merge 时只输出索引：按源文件列出每个函数的签名与简介，并链接到各自文档中的锚点")]
+    pub toc_only: bool,
+
+    #[arg(short, long, conflicts_with = "merge", help = "生成后继续监视输入文件，文件变化时重新生成文档")]
+    pub watch: bool,
+
+    #[arg(long, conflicts_with = "watch", help = "完整解析但不写任何文件，只列出每个输入的文档块数与输出路径；配合 -v 列出每个文档块的签名")]
+    pub dry_run: bool,
+}
+
+/// 参数列表的排序方式
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+pub enum ParamOrder {
+    /// 按函数签名中的参数顺序
+    Signature,
+}
+
+/// 生成文档的输出格式
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum OutputFormat {
+    /// Markdown 文档 (.md)
+    #[default]
+    Markdown,
+    /// 文档块的 JSON 数组 (.json)
+    Json,
+    /// 独立的 HTML 页面 (.html)，公式交给 MathJax 渲染
+    Html,
+}
+
+/// 诊断信息的输出格式
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default, PartialEq)]
+pub enum LogFormat {
+    /// 给人阅读的文本
+    #[default]
+    Text,
+    /// 每条诊断一行 JSON 对象 (NDJSON)，包含 level/file/line/code/message
+    Json,
+}
+
+/// 把 log 记录输出到 stderr 的简单日志器，级别由 -v / -q 决定
+struct StderrLogger;
+
+impl log::Log for StderrLogger {
+    fn enabled(&self, metadata: &log::Metadata) -> bool {
+        metadata.level() <= log::max_level()
+    }
+
+    fn log(&self, record: &log::Record) {
+        if !self.enabled(record.metadata()) {
+            return;
+        }
+        match record.level() {
+            log::Level::Error => eprintln!("错误: {}", record.args()),
+            log::Level::Warn => eprintln!("警告: {}", record.args()),
+            _ => eprintln!("{}", record.args()),
+        }
+    }
+
+    fn flush(&self) {}
+}
+
+static LOGGER: StderrLogger = StderrLogger;
+
+/// 根据 -v / -q 安装日志器：默认只输出警告与错误
+fn init_logging(args: &Args) {
+    let level = match (args.quiet, args.verbose) {
+        (true, _) => log::LevelFilter::Error,
+        (false, 0) => log::LevelFilter::Warn,
+        (false, 1) => log::LevelFilter::Info,
+        (false, 2) => log::LevelFilter::Debug,
+        (false, _) => log::LevelFilter::Trace,
+    };
+    if log::set_logger(&LOGGER).is_ok() {
+        log::set_max_level(level);
+    }
 }
 
-/// 保存 Markdown 文件
-fn save_markdown_file(path: &Path, content: &str) -> std::io::Result<()> {
-    let mut file = File::create(path)?;
+/// 一次运行中使用的输出格式化器
+struct Output {
+    /// 合并文档的标题等文档级内容使用的 Markdown 格式化器
+    markdown: MarkdownFormatter,
+    /// 渲染每个文件文档块的格式化器
+    formatter: Box<dyn OutputFileFormatter + Send + Sync>,
+    /// 生成文件的扩展名
+    extension: &'static str,
+}
+
+impl Output {
+    /// 按 --format 选择格式化器与生成文件的扩展名
+    ///
+    /// 所有输出格式都在这里分派，新增格式只需增加一个分支（以及 OutputFormat 的一个变体）
+    fn new(args: &Args) -> Result<Self, String> {
+        let (formatter, extension): (Box<dyn OutputFileFormatter + Send + Sync>, _) = match args.format {
+            OutputFormat::Markdown => match &args.template_dir {
+                Some(dir) => {
+                    let templates = TemplateFormatter::new(dir, markdown_formatter(args))
+                        .map_err(|e| format!("加载模板失败: {:?}", e))?;
+                    (Box::new(templates), "md")
+                }
+                None => (Box::new(markdown_formatter(args)), "md"),
+            },
+            OutputFormat::Json => (Box::new(JsonFormatter { include_private: args.include_private }), "json"),
+            OutputFormat::Html => (Box::new(HtmlFormatter { include_private: args.include_private }), "html"),
+        };
+        Ok(Output { markdown: markdown_formatter(args), formatter, extension })
+    }
+
+    fn render(&self, source: &Path, blocks: &[DocBlock]) -> Result<String, String> {
+        generate(source, blocks, self.formatter.as_ref(), None)
+    }
+}
+
+/// 一次运行中累积的状态
+#[derive(Default)]
+struct RunState {
+    /// 所有文件的解析结果
+    docs: Vec<FileDoc>,
+    /// 已写出的输出路径 -> 生成它的源文件
+    outputs: HashMap<PathBuf, PathBuf>,
+    /// 输出路径冲突的次数
+    collisions: usize,
+    /// 诊断信息的输出格式
+    log_format: LogFormat,
+    /// 已输出的诊断信息条数（警告及以上）
+    diagnostics: usize,
+    /// --merge 模式下收集的 (源文件, 渲染结果)
+    merged: Vec<(PathBuf, String)>,
+}
+
+impl RunState {
+    /// 立即把一条诊断信息输出到 stderr
+    fn report(&mut self, diag: Diagnostic) {
+        self.diagnostics += 1;
+        // --quiet 时警告仍然计数（影响 --fail-on-warning），只是不输出
+        if diag.level == Level::Warning && !log::log_enabled!(log::Level::Warn) {
+            return;
+        }
+        match self.log_format {
+            LogFormat::Text => eprintln!("{}", diag),
+            LogFormat::Json => eprintln!("{}", diag.to_json_line()),
+        }
+    }
+}
+
+/// 根据命令行参数构造 Markdown 格式化器
+fn markdown_formatter(args: &Args) -> MarkdownFormatter {
+    MarkdownFormatter {
+        badges: !args.no_badges,
+        file_toc: !args.no_toc,
+        heading_offset: args.heading_offset,
+        signature: !args.no_signature,
+        style: args.style,
+        param_style: args.param_style,
+        escape_text: !args.raw_markdown,
+        front_matter: args.front_matter,
+        self_param: args.self_param,
+        include_private: args.include_private,
+    }
+}
+
+/// 保存 Markdown 文件，append 为 true 时追加到已有文件末尾
+fn save_markdown_file(path: &Path, content: &str, append: bool) -> std::io::Result<()> {
+    let mut file = OpenOptions::new()
+        .write(true)
+        .create(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)?;
     file.write_all(content.as_bytes())?;
     Ok(())
 }
 
+/// 把 --merge 收集的各文件文档拼接为一个文档：每个源文件一个二级标题
+///
+/// 各节按源文件路径排序，与扫描和并行处理的顺序无关，多次运行的输出因此保持一致
+///
+/// 文档级的一级标题只在新建文件时输出，追加时省略，多次运行的结果因此能拼成一个文档
+fn merged_document(merge_path: &Path, append: bool, sections: &[(PathBuf, String)], output: &Output) -> String {
+    let mut sections: Vec<&(PathBuf, String)> = sections.iter().collect();
+    sections.sort_by(|a, b| a.0.cmp(&b.0));
+    let mut s = String::new();
+    let continuing = append && merge_path.metadata().is_ok_and(|m| m.len() > 0);
+    if !continuing {
+        let title = merge_path.file_stem().and_then(|s| s.to_str()).unwrap_or("API");
+        s.push_str(&output.markdown.heading(1, title));
+    }
+    for (path, content) in sections {
+        s.push_str(&output.markdown.heading(2, &path.display().to_string()));
+        s.push_str(content);
+    }
+    s
+}
+
+/// 源文件对应的输出路径 (filename.md，扩展名随输出格式变化)
+///
+/// 默认写在源文件旁；指定 --output-dir 时写入该目录，递归扫描时保留相对当前目录的子目录结构。
+/// 指定 --output 时（只有一个输入文件）原样使用该路径
+fn output_path(path: &Path, args: &Args, extension: &str) -> PathBuf {
+    if let Some(output) = &args.output {
+        return output.clone();
+    }
+    let mut out_path = match &args.output_dir {
+        Some(dir) => {
+            let cwd = env::current_dir().unwrap_or(PathBuf::from("."));
+            let rel = match path.strip_prefix(&cwd) {
+                Ok(rel) if args.recursive => rel.to_path_buf(),
+                _ => PathBuf::from(path.file_name().unwrap_or(path.as_os_str())),
+            };
+            dir.join(rel)
+        }
+        None => path.to_path_buf(),
+    };
+    out_path.set_extension(extension);
+    out_path
+}
+
 /// 处理单个文件
-fn process_single_file(path: &Path) {
-    println!("-----------------------------------------------------");
-    println!("正在处理文件: {}", path.display());
+fn process_single_file(path: &Path, langs: &LanguageOverrides, args: &Args, output: &Output, state: &mut RunState) {
+    log::info!("正在处理文件: {}", path.display());
 
     if !path.exists() {
-        eprintln!("错误: 文件不存在: {}", path.display());
+        state.report(Diagnostic::error(path, "file-not-found", "文件不存在"));
         return;
     }
 
-    // 1. 推断文件类型
-    let extension = path
-        .extension()
-        .and_then(|e| e.to_str())
-        .unwrap_or("");
-    
-    let file_type = InputFileType::from_str(extension);
-    
-    // 安全地获取类型名称用于打印
-    let type_name = file_type.as_ref()
-        .and_then(|t| t.to_str())
-        .unwrap_or("Unknown");
-    println!("文件类型: {:?}", type_name);
-
-    // 检查是否是不支持的类型
-    if file_type.is_none() || matches!(file_type, Some(InputFileType::None)) {
-        println!("跳过不支持的文件类型: {}", path.display());
-        return;
+    let result = document_file(path, langs).and_then(|doc| emit_file_doc(doc, args, output, state));
+    match result {
+        Ok(()) => {}
+        Err(ToDocError::UnsupportedLanguage(_)) => {
+            log::info!("跳过不支持的文件类型: {}", path.display());
+        }
+        Err(e) => state.report(Diagnostic::from_error(path, &e)),
     }
+}
 
-    // 2. 创建解析器并解析 Is it a parser? Yes!
-    // create_file_parser 接受 &Option<InputFileType>
-    let parser = create_file_parser(&file_type);
-    
-    let file = match File::open(path) {
-        Ok(f) => f,
-        Err(e) => {
-            eprintln!("无法打开文件: {}", e);
-            return;
+/// 单个文件在写出之前的处理结果
+struct PreparedDoc {
+    doc: FileDoc,
+    /// 检查过程中产生的警告
+    diagnostics: Vec<Diagnostic>,
+    /// 渲染结果，文件中没有文档块时为 None
+    rendered: Option<Result<String, ToDocError>>,
+}
+
+/// 对解析好的文件做后续检查并格式化，不修改任何共享状态，可以并行执行
+fn prepare_file_doc(mut doc: FileDoc, args: &Args, output: &Output) -> PreparedDoc {
+    let path = doc.path.clone();
+    let mut diagnostics = Vec::new();
+    log::debug!("文件类型: {:?}", doc.lang.to_str().unwrap_or("Unknown"));
+
+    if doc.blocks.is_empty() {
+        log::info!("{}: 未发现文档块，跳过生成.", path.display());
+        return PreparedDoc { doc, diagnostics, rendered: None };
+    }
+    log::info!("{}: 发现 {} 个文档块.", path.display(), doc.blocks.len());
+
+    for name in load_examples(&mut doc, args.examples_dir.as_deref()) {
+        diagnostics.push(Diagnostic::warning(&path, "missing-example", format!("找不到示例文件 {}", name)));
+    }
+
+    // 解析时发现的问题，以及没有任何简介或描述的文档块；解析器记录了位置时指向文档注释的第一行
+    for block in doc.blocks.iter_mut() {
+        let name = block.function_name();
+        let start_line = block.start_line;
+        let located = |diag: Diagnostic| if start_line > 0 { diag.at_line(start_line) } else { diag };
+        for warning in std::mem::take(&mut block.warnings) {
+            let message = format!("{}: {}", name, warning.message);
+            diagnostics.push(located(Diagnostic::warning(&path, warning.code, message)));
+        }
+        if block.brief.is_empty() && block.descriptions.is_empty() {
+            diagnostics.push(located(Diagnostic::warning(&path, "missing-brief", format!("{}: 缺少简介", name))));
+        }
+    }
+
+    if let Some(ParamOrder::Signature) = args.sort_params {
+        for block in doc.blocks.iter_mut() {
+            for note in block.sort_params_by_signature() {
+                diagnostics.push(Diagnostic::warning(&path, "param-order", format!("{}: {}", block.function_name(), note)));
+            }
+        }
+    }
+
+    if !args.include_dirs.is_empty() {
+        let source_dir = path.parent().unwrap_or(Path::new("."));
+        for block in &doc.blocks {
+            for inc in unresolved_includes(block, source_dir, &args.include_dirs) {
+                let message = format!("找不到 {} 引用的头文件 {}", block.function_name(), inc);
+                diagnostics.push(Diagnostic::warning(&path, "unresolved-include", message));
+            }
         }
+    }
+
+    // 格式化为 Markdown
+    let rendered = output.render(&path, &doc.blocks).map_err(|e| ToDocError::Render(path, e));
+    PreparedDoc { doc, diagnostics, rendered: Some(rendered) }
+}
+
+/// 输出 prepare_file_doc 的诊断并写出文档，解析结果记入 state
+///
+/// 输出路径冲突只记入 state.collisions；格式化或写入失败时返回错误
+fn commit_file_doc(prepared: PreparedDoc, args: &Args, output: &Output, state: &mut RunState) -> Result<(), ToDocError> {
+    let PreparedDoc { doc, diagnostics, rendered } = prepared;
+    let path = doc.path.clone();
+    for diag in diagnostics {
+        state.report(diag);
+    }
+    if args.dry_run {
+        let target = rendered.is_some().then(|| output_target(&path, args, output));
+        print_dry_run(&doc, target.as_deref(), args);
+        state.docs.push(doc);
+        return Ok(());
+    }
+    // --toc-only：合并文档中只放索引
+    let index = match (&args.merge, args.toc_only) {
+        (Some(merge_path), true) => Some(output.markdown.format_index(&doc.blocks, &index_link(&path, merge_path, args, output))),
+        _ => None,
     };
+    state.docs.push(doc);
+    let markdown_content = match rendered {
+        Some(rendered) => rendered?,
+        None => return Ok(()),
+    };
+    if args.merge.is_some() {
+        state.merged.push((path, index.unwrap_or(markdown_content)));
+        return Ok(());
+    }
+    if args.stdout {
+        print_to_stdout(&path, &markdown_content, args);
+        return Ok(());
+    }
 
-    let doc_blocks = parser.parse(&file);
-    if doc_blocks.is_empty() {
-        println!("未发现文档块，跳过生成.");
-        return;
+    let out_path = output_path(&path, args, output.extension);
+
+    // 不同的源文件映射到同一个输出路径时，不覆盖先生成的文档
+    let key = std::path::absolute(&out_path).unwrap_or_else(|_| out_path.clone());
+    let source = std::path::absolute(&path).unwrap_or_else(|_| path.clone());
+    if let Some(first) = state.outputs.get(&key).filter(|first| **first != source) {
+        let message = format!("与 {} 的输出路径冲突: {}，跳过写入", first.display(), out_path.display());
+        state.report(Diagnostic::error(&path, "output-collision", message));
+        state.collisions += 1;
+        return Ok(());
     }
-    println!("发现 {} 个文档块.", doc_blocks.len());
-
-    // 3. 格式化为 Markdown
-    let formatter = MarkdownFormatter {};
-    match formatter.format(doc_blocks) {
-        Ok(markdown_content) => {
-            // 4. 生成输出路径 (filename.md)
-            let mut out_path = PathBuf::from(path);
-            out_path.set_extension("md");
-            
-            // 5. 写入文件
-            match save_markdown_file(&out_path, &markdown_content) {
-                Ok(_) => println!("成功生成文档: {}", out_path.display()),
-                Err(e) => eprintln!("写入文件失败: {}", e),
+    state.outputs.insert(key, source);
+    if let Some(parent) = out_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent).map_err(|e| ToDocError::Io(parent.to_path_buf(), e))?;
+    }
+    // 写入文件
+    save_markdown_file(&out_path, &markdown_content, false).map_err(|e| ToDocError::Io(out_path.clone(), e))?;
+    log::info!("成功生成文档: {}", out_path.display());
+    Ok(())
+}
+
+/// --toc-only 索引中指向源文件完整文档的链接：该文档的输出路径，表示为相对合并文件所在目录的路径
+fn index_link(path: &Path, merge_path: &Path, args: &Args, output: &Output) -> String {
+    let absolute = |p: &Path| std::path::absolute(p).unwrap_or_else(|_| p.to_path_buf());
+    let target = absolute(&output_path(path, args, output.extension));
+    let base = absolute(merge_path.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(Path::new(".")));
+    // 去掉共同的前缀，base 余下的每一层换成 `..`（不同盘符等没有共同前缀时保留绝对路径）
+    let common = target.components().zip(base.components()).take_while(|(a, b)| a == b).count();
+    if common == 0 {
+        return target.display().to_string().replace('\\', "/");
+    }
+    let mut link = PathBuf::new();
+    for _ in base.components().skip(common) {
+        link.push("..");
+    }
+    link.extend(target.components().skip(common));
+    link.display().to_string().replace('\\', "/")
+}
+
+/// 文档实际会写到哪里（--merge 的文件、标准输出或单独的输出文件），用于 --dry-run 的报告
+fn output_target(path: &Path, args: &Args, output: &Output) -> String {
+    match (&args.merge, args.stdout) {
+        (Some(merge_path), _) => format!("{} (合并)", merge_path.display()),
+        (None, true) => "标准输出".to_string(),
+        (None, false) => output_path(path, args, output.extension).display().to_string(),
+    }
+}
+
+/// --dry-run：报告一个文件的文档块数与输出位置（没有文档块时不生成文件），-v 时列出每个签名
+fn print_dry_run(doc: &FileDoc, target: Option<&str>, args: &Args) {
+    let mut stdout = std::io::stdout().lock();
+    let _ = match target {
+        Some(target) => writeln!(stdout, "{}: {} 个文档块 -> {}", doc.path.display(), doc.blocks.len(), target),
+        None => writeln!(stdout, "{}: 0 个文档块，不生成文档", doc.path.display()),
+    };
+    if args.verbose > 0 {
+        for block in &doc.blocks {
+            let _ = writeln!(stdout, "    {}", block.signature.raw);
+        }
+    }
+}
+
+/// --stdout：把一个文件的文档写到标准输出
+///
+/// 不止一个输入文件时，每个文件的内容前输出一行注明源文件的 HTML 注释（JSON 不支持注释，不输出）
+fn print_to_stdout(path: &Path, content: &str, args: &Args) {
+    let mut stdout = std::io::stdout().lock();
+    if args.files.len() != 1 && args.format == OutputFormat::Markdown {
+        let _ = writeln!(stdout, "<!-- {} -->\n", path.display());
+    }
+    let _ = stdout.write_all(content.as_bytes());
+}
+
+/// 对解析好的文件做后续检查，格式化并写出文档，解析结果记入 state
+fn emit_file_doc(doc: FileDoc, args: &Args, output: &Output, state: &mut RunState) -> Result<(), ToDocError> {
+    commit_file_doc(prepare_file_doc(doc, args, output), args, output, state)
+}
+
+/// 遍历目录处理文件，解析结果记入 state
+///
+/// 解析与格式化在线程池中并行进行（--jobs 限制线程数）；诊断输出、冲突检查与写文件
+/// 按扫描顺序依次进行，因此结果与串行处理时相同
+fn process_directory(dir: &Path, args: &Args, output: &Output, state: &mut RunState) -> Result<(), ToDocError> {
+    let tree = document_tree(dir, &scan_options(args))?;
+    for diag in tree.diagnostics {
+        state.report(diag);
+    }
+    for (path, e) in &tree.errors {
+        state.report(Diagnostic::from_error(path, e));
+    }
+    let prepared: Vec<PreparedDoc> = tree
+        .docs
+        .into_par_iter()
+        .map(|doc| prepare_file_doc(doc, args, output))
+        .collect();
+    for doc in prepared {
+        let path = doc.doc.path.clone();
+        if let Err(e) = commit_file_doc(doc, args, output, state) {
+            state.report(Diagnostic::from_error(&path, &e));
+        }
+    }
+    Ok(())
+}
+
+/// 根据命令行参数构造目录扫描选项
+fn scan_options(args: &Args) -> Options {
+    Options {
+        recursive: args.recursive,
+        max_depth: args.max_depth,
+        only: args.only.clone(),
+        exclude: args.exclude.clone(),
+        lang_for: args.lang_for.clone(),
+        no_ignore: args.no_ignore,
+    }
+}
+
+/// 监视输入文件（或 --all 时的当前目录），变化时重新生成对应文档
+fn watch_inputs(langs: &LanguageOverrides, args: &Args, output: &Output, state: &mut RunState) -> Result<(), ToDocError> {
+    let root = env::current_dir().unwrap_or(PathBuf::from("."));
+    let opts = scan_options(args);
+    let (paths, recursive) = if args.files.is_empty() {
+        (vec![root.clone()], args.recursive)
+    } else {
+        (args.files.iter().map(PathBuf::from).collect(), false)
+    };
+
+    // Ctrl-C 时结束监视并正常退出
+    let stop = Arc::new(AtomicBool::new(false));
+    let handler_stop = Arc::clone(&stop);
+    if let Err(e) = ctrlc::set_handler(move || handler_stop.store(true, Ordering::SeqCst)) {
+        log::warn!("无法注册 Ctrl-C 处理: {}", e);
+    }
+
+    log::info!("正在监视文件变化，按 Ctrl-C 退出...");
+    watch(&paths, recursive, &stop, |path| {
+        // 目录监视时只处理会被扫描选中的文件，生成的 .md 等文件不会触发重新生成
+        if args.files.is_empty() && !opts.selects(&root, path)? {
+            return Ok(false);
+        }
+        log::info!("正在重新生成: {}", path.display());
+        match document_file(path, langs) {
+            Ok(doc) => emit_file_doc(doc, args, output, state).map(|()| true),
+            Err(ToDocError::UnsupportedLanguage(_)) => Ok(false),
+            Err(e) => Err(e),
+        }
+    })?;
+    log::info!("已停止监视");
+    Ok(())
+}
+
+/// 展开 --files 中的通配符模式（`src/**/*.lua`），不依赖 shell 的展开
+///
+/// 不含通配符的路径原样保留（不存在的文件之后按文件不存在报告）；
+/// 结果按给出的顺序去重，没有匹配任何文件的模式给出警告
+fn expand_file_patterns(patterns: &[String], state: &mut RunState) -> Result<Vec<String>, String> {
+    let mut seen = HashSet::new();
+    let mut files = Vec::new();
+    for pattern in patterns {
+        if !pattern.contains(['*', '?', '[']) {
+            if seen.insert(pattern.clone()) {
+                files.push(pattern.clone());
             }
-        },
-        Err(e) => eprintln!("格式化 Markdown 失败: {}", e),
-    }
-}
-
-/// 递归遍历目录处理文件
-fn process_directory(dir: &Path, recursive: bool) {
-    if let Ok(entries) = std::fs::read_dir(dir) {
-        for entry in entries {
-            if let Ok(entry) = entry {
-                let path = entry.path();
-                if path.is_dir() {
-                    if recursive {
-                        process_directory(&path, recursive);
-                    }
-                } else {
-                    // 简单的过滤逻辑，只处理源码文件
-                    if let Some(ext) = path.extension().and_then(|s| s.to_str()) {
-                        if InputFileType::from_str(ext).is_some() {
-                           process_single_file(&path);
-                        }
-                    }
-                }
+            continue;
+        }
+        let paths = glob::glob(pattern).map_err(|e| format!("无效的文件模式 {}: {}", pattern, e))?;
+        let mut matched = 0;
+        for path in paths.flatten().filter(|p| p.is_file()) {
+            matched += 1;
+            let file = path.display().to_string();
+            if seen.insert(file.clone()) {
+                files.push(file);
             }
         }
+        if matched == 0 {
+            state.report(Diagnostic::warning(Path::new(pattern), "no-match", "没有匹配的文件"));
+        }
     }
+    Ok(files)
 }
 
-fn cmd_parser() {
-    let args = Args::parse();
+/// 读取 --files-from 的清单：每行一个路径（可含通配符），忽略空行与 `#` 开头的注释行，
+/// 相对路径按清单文件所在目录解析
+fn read_file_list(list: &Path) -> Result<Vec<String>, String> {
+    let content = std::fs::read_to_string(list).map_err(|e| format!("无法读取文件清单 {}: {}", list.display(), e))?;
+    let base = list.parent().unwrap_or(Path::new(""));
+    Ok(content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(|line| base.join(line).display().to_string())
+        .collect())
+}
+
+/// 解析命令行并执行，返回进程退出码
+fn cmd_parser() -> i32 {
+    let mut args = Args::parse();
+    init_logging(&args);
+    // 简化的入口检查，不再强制检查程序名，方便 cargo run 调试
+    if let Some(exe) = env::args().next() {
+        log::debug!("Running: {}", exe);
+    }
+    if let Some(jobs) = args.jobs {
+        if let Err(e) = rayon::ThreadPoolBuilder::new().num_threads(jobs).build_global() {
+            eprintln!("错误: 无法创建线程池: {}", e);
+            return 2;
+        }
+    }
+    let mut state = RunState { log_format: args.log_format, ..RunState::default() };
+    if let Some(list) = &args.files_from {
+        match read_file_list(list) {
+            Ok(files) => args.files.extend(files),
+            Err(e) => {
+                eprintln!("错误: {}", e);
+                return 2;
+            }
+        }
+    }
+    let explicit_files = !args.files.is_empty();
+    args.files = match expand_file_patterns(&args.files, &mut state) {
+        Ok(files) => files,
+        Err(e) => {
+            eprintln!("错误: {}", e);
+            return 2;
+        }
+    };
+    if args.output.is_some() && args.files.len() != 1 {
+        eprintln!("错误: --output 只能用于单个输入文件，当前有 {} 个输入文件", args.files.len());
+        return 2;
+    }
+    if args.merge.is_some() && args.format != OutputFormat::Markdown {
+        eprintln!("错误: --merge 只支持 markdown 输出格式");
+        return 2;
+    }
+    let output = match Output::new(&args) {
+        Ok(output) => output,
+        Err(e) => {
+            eprintln!("错误: {}", e);
+            return 2;
+        }
+    };
+    let langs = match LanguageOverrides::new(&args.lang_for) {
+        Ok(langs) => langs,
+        Err(e) => {
+            eprintln!("错误: {}", e);
+            return 2;
+        }
+    };
+
+    if let Some(dir) = args.output_dir.as_ref().filter(|_| !args.dry_run) {
+        if let Err(e) = std::fs::create_dir_all(dir) {
+            eprintln!("错误: 无法创建输出目录 {}: {}", dir.display(), e);
+            return 2;
+        }
+    }
 
     // 1. 如果指定了具体文件，优先处理
-    if !args.files.is_empty() {
+    if explicit_files {
         for file_name in &args.files {
             let path = Path::new(file_name);
-            process_single_file(path);
+            process_single_file(path, &langs, &args, &output, &mut state);
         }
     } 
     // 2. 否则如果指定了 --all，遍历目录
     else if args.all {
         let current_dir = env::current_dir().unwrap_or(PathBuf::from("."));
-        println!("正在扫描目录: {}", current_dir.display());
-        process_directory(&current_dir, args.recursive);
+        log::info!("正在扫描目录: {}", current_dir.display());
+        if let Err(e) = process_directory(&current_dir, &args, &output, &mut state) {
+            eprintln!("错误: {}", e);
+            return 2;
+        }
     } 
     // 3. 无参数提示
     else {
         println!("未指定输入文件。使用 --files <path> 或 --all 运行。");
         println!("尝试运行 'todoc --help' 查看更多选项。");
+        return 0;
     }
-}
 
-fn main() {
-    // 简化的入口检查，不再强制检查程序名，方便 cargo run 调试
-    let args: Vec<String> = env::args().collect();
-    if let Some(exe) = args.first() {
-        // 可以在这里做日志
-        println!("Running: {}", exe);
+    if args.dry_run {
+        let block_count: usize = state.docs.iter().map(|doc| doc.blocks.len()).sum();
+        println!("共 {} 个文件，{} 个文档块（--dry-run，未写入任何文件）", state.docs.len(), block_count);
+        return if state.diagnostics > 0 && args.fail_on_warning { 1 } else { 0 };
+    }
+
+    if let Some(merge_path) = &args.merge {
+        let content = merged_document(merge_path, args.append, &state.merged, &output);
+        match save_markdown_file(merge_path, &content, args.append) {
+            Ok(_) => log::info!("成功生成合并文档: {} ({} 个文件)", merge_path.display(), state.merged.len()),
+            Err(e) => {
+                eprintln!("错误: 写入 {} 失败: {}", merge_path.display(), e);
+                return 2;
+            }
+        }
+    }
+
+    if let Some(csv_path) = &args.params_csv {
+        let written = params_to_csv(&state.docs)
+            .map_err(|e| e.to_string())
+            .and_then(|csv| save_markdown_file(Path::new(csv_path), &csv, false).map_err(|e| e.to_string()));
+        match written {
+            Ok(_) => log::info!("成功导出参数表: {}", csv_path),
+            Err(e) => eprintln!("导出参数表失败: {}", e),
+        }
+    }
+
+    if let Some(todo_path) = &args.todo_report {
+        match save_markdown_file(todo_path, &todo_report(&state.docs), false) {
+            Ok(_) => log::info!("成功导出待办报告: {}", todo_path.display()),
+            Err(e) => eprintln!("导出待办报告失败: {}", e),
+        }
+    }
+
+    if let Some(dot_path) = &args.include_graph {
+        match save_markdown_file(dot_path, &include_graph(&state.docs), false) {
+            Ok(_) => log::info!("成功导出包含关系图: {}", dot_path.display()),
+            Err(e) => eprintln!("导出包含关系图失败: {}", e),
+        }
     }
 
-    cmd_parser();
-    
-    println!("-----------------------------------------------------");
-    println!("任务完成.");
+    let block_count: usize = state.docs.iter().map(|doc| doc.blocks.len()).sum();
+    if args.require_docs && block_count == 0 {
+        eprintln!("错误: 未在任何文件中发现文档块 (--require-docs)");
+        return 1;
+    }
+    if state.collisions > 0 {
+        eprintln!("错误: {} 个文件因输出路径冲突未能生成文档", state.collisions);
+        return 1;
+    }
+    // 汇总行只是给人看的，JSON 格式时 stderr 只输出诊断信息本身
+    let summary = args.log_format == LogFormat::Text && log::log_enabled!(log::Level::Warn);
+    if state.diagnostics > 0 && !args.fail_on_warning && summary {
+        eprintln!("共有 {} 条警告或错误", state.diagnostics);
+    }
+    if args.fail_on_warning && state.diagnostics > 0 {
+        let message = format!("共有 {} 条警告或错误 (--fail-on-warning)", state.diagnostics);
+        state.report(Diagnostic::global(Level::Error, "fail-on-warning", message));
+        return 1;
+    }
+    if args.watch {
+        if let Err(e) = watch_inputs(&langs, &args, &output, &mut state) {
+            eprintln!("错误: {}", e);
+            return 2;
+        }
+    }
+    0
+}
+
+fn main() {
+    let code = cmd_parser();
+    log::info!("任务完成.");
+    std::process::exit(code);
 }
@@ -0,0 +1,203 @@
+use crate::file_parser::{Description, DescriptionType, DocBlock, MarkdownFormatter, OutputFileFormatter, Parameter};
+use std::path::Path;
+use tera::{Context, Map, Tera, Value};
+
+/// 基于模板目录的格式化器（tera 模板引擎）
+///
+/// 模板目录中按 "用途.格式.tera" 命名的文件会被加载：
+/// - `block.md.tera`：渲染单个文档块
+/// - `index.md.tera`：渲染整个输出文件
+///
+/// 缺少的模板回退到内置的 MarkdownFormatter（缺少 block 模板时整个文件交由它格式化）；
+/// 是否输出私有的文档块同样取自该格式化器的设置
+///
+/// block 模板可用的变量：
+/// - `signature`, `name`, `brief`, `note`, `owner`：字符串
+/// - `includes`, `aliases`, `todos`, `see`, `generics`：字符串列表
+/// - `params`, `properties`, `fields`, `yields`, `returns`, `errors`：列表，每项包含 `name`, `number`（从 0 开始的序号）, `type`, `description`, `direction`, `optional`,
+///   以及 `details`（子标签内容，格式同 `descriptions`）
+/// - `descriptions`：列表，每项包含 `kind` (text/code/formula/list/html/note，note 即 @note) 与 `content`
+///   （`content` 为空的 text 项表示段落分隔）
+/// - `since`, `complexity`, `example`, `module`, `group`, `class`, `author`, `version`：字符串或 null
+/// - `receiver`：`:` 定义的方法隐含的 self 参数（格式同 `params` 的项），其他函数为 null
+/// - `deprecated`：弃用说明（没有说明时为空字符串），未弃用时为 null
+/// - `is_local`, `is_member`, `is_async`：布尔值
+/// - `lang`：源文件语言 (lua/c/...)
+///
+/// index 模板可用的变量：
+/// - `content`：所有文档块渲染结果拼接而成的文本
+/// - `blocks`：每个文档块的变量（同 block 模板）组成的列表
+pub struct TemplateFormatter {
+    tera: Tera,
+    fallback: MarkdownFormatter,
+}
+
+impl TemplateFormatter {
+    pub const BLOCK_TEMPLATE: &'static str = "block.md.tera";
+    pub const INDEX_TEMPLATE: &'static str = "index.md.tera";
+
+    /// 加载目录下所有 `.tera` 模板，模板名为文件名
+    pub fn new(dir: &Path, fallback: MarkdownFormatter) -> Result<Self, tera::Error> {
+        let mut tera = Tera::default();
+        let entries = std::fs::read_dir(dir)
+            .map_err(|e| tera::Error::msg(format!("无法读取模板目录 {}: {}", dir.display(), e)))?;
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) != Some("tera") {
+                continue;
+            }
+            if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+                tera.add_template_file(&path, Some(name))?;
+            }
+        }
+        Ok(TemplateFormatter { tera, fallback })
+    }
+
+    fn has_template(&self, name: &str) -> bool {
+        self.tera.get_template_names().any(|n| n == name)
+    }
+
+    fn render(&self, blocks: &[DocBlock]) -> Result<String, tera::Error> {
+        let values: Vec<Value> = blocks
+            .iter()
+            .filter(|b| self.fallback.include_private || !b.is_local)
+            .map(block_value)
+            .collect();
+        let content = if self.has_template(Self::BLOCK_TEMPLATE) {
+            let mut content = String::new();
+            for value in &values {
+                let context = Context::from_value(value.clone())?;
+                content.push_str(&self.tera.render(Self::BLOCK_TEMPLATE, &context)?);
+            }
+            content
+        } else {
+            // 一次格式化所有文档块，@see 链接的锚点与分组标题才能跨文档块生效
+            self.fallback.format(blocks).map_err(tera::Error::msg)?
+        };
+
+        if !self.has_template(Self::INDEX_TEMPLATE) {
+            return Ok(content);
+        }
+        let mut context = Context::new();
+        context.insert("content", &content);
+        context.insert("blocks", &values);
+        self.tera.render(Self::INDEX_TEMPLATE, &context)
+    }
+}
+
+impl OutputFileFormatter for TemplateFormatter {
+    fn format(&self, content: &[DocBlock]) -> Result<String, String> {
+        self.render(content).map_err(|e| format!("{:?}", e))
+    }
+}
+
+fn string_value(s: &str) -> Value {
+    Value::String(s.to_string())
+}
+
+fn optional_value(s: &Option<String>) -> Value {
+    s.as_deref().map_or(Value::Null, string_value)
+}
+
+fn parameter_value(p: &Parameter) -> Value {
+    let mut m = Map::new();
+    m.insert("name".into(), string_value(&p.name));
+    m.insert("number".into(), Value::from(p.number));
+    m.insert("type".into(), string_value(&p.type_name));
+    m.insert("description".into(), string_value(&p.description));
+    m.insert("direction".into(), optional_value(&p.direction));
+    m.insert("optional".into(), Value::Bool(p.optional));
+    m.insert(
+        "details".into(),
+        Value::Array(p.details.iter().map(description_value).collect()),
+    );
+    Value::Object(m)
+}
+
+fn description_value(d: &Description) -> Value {
+    let kind = match d.dtype {
+        DescriptionType::Text(_) => "text",
+        DescriptionType::Code(_, _) => "code",
+        DescriptionType::MathFormula(_, _) => "formula",
+        DescriptionType::BulletList(_, _) => "list",
+        DescriptionType::HTMLLink(_) => "html",
+        DescriptionType::Note(_) => "note",
+    };
+    let mut m = Map::new();
+    m.insert("kind".into(), string_value(kind));
+    m.insert("content".into(), string_value(&d.content));
+    Value::Object(m)
+}
+
+/// 把 DocBlock 转换为模板变量
+pub fn block_value(block: &DocBlock) -> Value {
+    let mut m = Map::new();
+    m.insert("signature".into(), string_value(&block.signature.raw));
+    m.insert("name".into(), string_value(&block.function_name()));
+    m.insert("brief".into(), string_value(&block.brief));
+    m.insert("note".into(), string_value(&block.note));
+    m.insert("owner".into(), string_value(&block.owner_object));
+    m.insert(
+        "includes".into(),
+        Value::Array(block.includes.iter().map(|s| string_value(s)).collect()),
+    );
+    m.insert(
+        "aliases".into(),
+        Value::Array(block.aliases.iter().map(|s| string_value(s)).collect()),
+    );
+    m.insert(
+        "todos".into(),
+        Value::Array(block.todos.iter().map(|s| string_value(s)).collect()),
+    );
+    m.insert(
+        "see".into(),
+        Value::Array(block.see.iter().map(|s| string_value(s)).collect()),
+    );
+    m.insert(
+        "generics".into(),
+        Value::Array(block.generics.iter().map(|s| string_value(s)).collect()),
+    );
+    m.insert(
+        "params".into(),
+        Value::Array(block.parameters.iter().map(parameter_value).collect()),
+    );
+    m.insert(
+        "properties".into(),
+        Value::Array(block.properties.iter().map(parameter_value).collect()),
+    );
+    m.insert(
+        "fields".into(),
+        Value::Array(block.fields.iter().map(parameter_value).collect()),
+    );
+    m.insert(
+        "yields".into(),
+        Value::Array(block.yields.iter().map(parameter_value).collect()),
+    );
+    m.insert(
+        "returns".into(),
+        Value::Array(block.ret_value.iter().map(parameter_value).collect()),
+    );
+    m.insert(
+        "errors".into(),
+        Value::Array(block.errors.iter().map(parameter_value).collect()),
+    );
+    m.insert(
+        "descriptions".into(),
+        Value::Array(block.descriptions.iter().map(description_value).collect()),
+    );
+    m.insert("since".into(), optional_value(&block.since));
+    m.insert("complexity".into(), optional_value(&block.complexity));
+    m.insert("example".into(), optional_value(&block.example));
+    m.insert("module".into(), optional_value(&block.module));
+    m.insert("group".into(), optional_value(&block.group));
+    m.insert("class".into(), optional_value(&block.class));
+    m.insert("author".into(), optional_value(&block.author));
+    m.insert("version".into(), optional_value(&block.version));
+    m.insert("deprecated".into(), optional_value(&block.deprecated));
+    m.insert("receiver".into(), block.receiver.as_ref().map_or(Value::Null, parameter_value));
+    m.insert("is_local".into(), Value::Bool(block.is_local));
+    m.insert("is_member".into(), Value::Bool(block.is_member));
+    m.insert("is_async".into(), Value::Bool(block.is_async));
+    m.insert("lang".into(), string_value(block.lang.to_str().unwrap_or("")));
+    Value::Object(m)
+}
@@ -0,0 +1,139 @@
+use crate::ToDocError;
+use notify::{EventKind, RecursiveMode, Watcher};
+use std::collections::HashSet;
+use std::panic::{self, AssertUnwindSafe};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::Duration;
+
+const RED: &str = "\x1b[31m";
+const GREEN: &str = "\x1b[32m";
+const RESET: &str = "\x1b[0m";
+
+/// 最后一个文件事件之后等待的时间，编辑器保存一次文件常常产生多个事件，合并为一次重新生成
+const DEBOUNCE: Duration = Duration::from_millis(200);
+
+/// 监视期间的状态：记录哪些文件上一次重新生成失败
+///
+/// 单个文件出错（返回错误或发生 panic）只会打印醒目的错误信息，不会中断监视；
+/// 该文件之后再次成功生成时打印一条恢复信息，否则打印一条简短的重新生成信息
+#[derive(Default)]
+pub struct WatchState {
+    failing: HashSet<PathBuf>,
+}
+
+impl WatchState {
+    /// 调用 regenerate 重新生成 path 并处理结果，返回需要输出给用户的提示（若有）
+    ///
+    /// regenerate 返回 Ok(false) 表示该文件不需要处理（如不是被扫描的源文件），此时没有提示
+    pub fn regenerate<F>(&mut self, path: &Path, regenerate: F) -> Option<String>
+    where
+        F: FnOnce(&Path) -> Result<bool, ToDocError>,
+    {
+        let result = panic::catch_unwind(AssertUnwindSafe(|| regenerate(path)))
+            .unwrap_or_else(|cause| Err(ToDocError::Panic(path.to_path_buf(), panic_message(&cause))));
+        match result {
+            Ok(false) => None,
+            Ok(true) if self.failing.remove(path) => Some(format!("{}✔ 已恢复: {}{}", GREEN, path.display(), RESET)),
+            Ok(true) => Some(format!("✔ 已重新生成: {}", path.display())),
+            Err(e) => {
+                self.failing.insert(path.to_path_buf());
+                Some(format!("{}✘ 重新生成失败: {}{}", RED, e, RESET))
+            }
+        }
+    }
+
+    /// 当前处于失败状态的文件
+    pub fn failing(&self) -> impl Iterator<Item = &Path> {
+        self.failing.iter().map(PathBuf::as_path)
+    }
+}
+
+fn panic_message(cause: &Box<dyn std::any::Any + Send>) -> String {
+    cause
+        .downcast_ref::<&str>()
+        .map(|s| s.to_string())
+        .or_else(|| cause.downcast_ref::<String>().cloned())
+        .unwrap_or_else(|| "未知错误".to_string())
+}
+
+/// 监视 paths（文件或目录），其中的文件被修改或创建时调用 regenerate
+///
+/// 短时间内连续的事件会被合并，每个文件只重新生成一次。该函数会一直阻塞，
+/// 直到 stop 被置位（如收到 Ctrl-C）时正常返回；单个文件的错误由 WatchState 处理，不会使其返回
+pub fn watch<F>(paths: &[PathBuf], recursive: bool, stop: &AtomicBool, mut regenerate: F) -> Result<(), ToDocError>
+where
+    F: FnMut(&Path) -> Result<bool, ToDocError>,
+{
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    let mode = if recursive { RecursiveMode::Recursive } else { RecursiveMode::NonRecursive };
+    for path in paths {
+        watcher.watch(path, mode)?;
+    }
+
+    let mut state = WatchState::default();
+    // 等待合并的文件，保持事件到达的顺序
+    let mut pending: Vec<PathBuf> = Vec::new();
+    while !stop.load(Ordering::SeqCst) {
+        let event = match rx.recv_timeout(DEBOUNCE) {
+            Ok(Ok(event)) => event,
+            Ok(Err(e)) => {
+                eprintln!("{}✘ 监视出错: {}{}", RED, e, RESET);
+                continue;
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                for path in pending.drain(..).filter(|p| p.is_file()) {
+                    if let Some(message) = state.regenerate(&path, &mut regenerate) {
+                        eprintln!("{}", message);
+                    }
+                }
+                continue;
+            }
+            Err(RecvTimeoutError::Disconnected) => break,
+        };
+        if !matches!(event.kind, EventKind::Modify(_) | EventKind::Create(_)) {
+            continue;
+        }
+        for path in event.paths {
+            if !pending.contains(&path) {
+                pending.push(path);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 文件先生成失败、修复后重新生成成功：失败时记入 failing，成功后移出并提示已恢复
+    #[test]
+    fn recovers_after_error() {
+        let path = Path::new("broken.lua");
+        let mut state = WatchState::default();
+
+        let failed = state.regenerate(path, |p| Err(ToDocError::Render(p.to_path_buf(), "语法错误".to_string())));
+        assert!(failed.is_some_and(|m| m.contains("重新生成失败")));
+        assert_eq!(state.failing().collect::<Vec<_>>(), vec![path]);
+
+        let panicked = state.regenerate(path, |_| panic!("解析器崩溃"));
+        assert!(panicked.is_some_and(|m| m.contains("解析器崩溃")));
+        assert_eq!(state.failing().count(), 1);
+
+        let mut regenerated = false;
+        let recovered = state.regenerate(path, |_| {
+            regenerated = true;
+            Ok(true)
+        });
+        assert!(regenerated);
+        assert!(recovered.is_some_and(|m| m.contains("已恢复")));
+        assert_eq!(state.failing().count(), 0);
+
+        let again = state.regenerate(path, |_| Ok(true));
+        assert!(again.is_some_and(|m| m.contains("已重新生成")));
+        assert_eq!(state.regenerate(Path::new("other.txt"), |_| Ok(false)), None);
+    }
+}
@@ -0,0 +1,187 @@
+//! 命令行行为：直接运行编译好的 todocument，检查退出码与生成的文件
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+/// 建立一个空的临时目录
+fn fixture(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("todocument-cli-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&root).unwrap();
+    root
+}
+
+/// 在 dir 下运行 todocument
+fn run(dir: &Path, args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_todocument"))
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("无法运行 todocument")
+}
+
+/// 没有任何文档块时，--require-docs 以非零状态码退出；不指定时仍然成功
+#[test]
+fn require_docs_fails_when_nothing_is_documented() {
+    let root = fixture("require-docs");
+    fs::write(root.join("plain.lua"), "-- 普通注释\nfunction f() end\n").unwrap();
+
+    assert!(run(&root, &["--all"]).status.success());
+    let output = run(&root, &["--all", "--require-docs"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--require-docs"));
+
+    fs::write(root.join("doc.lua"), "--- @brief g\nfunction g() end\n").unwrap();
+    assert!(run(&root, &["--all", "--require-docs"]).status.success());
+    fs::remove_dir_all(&root).unwrap();
+}
+
+/// 不同目录下的同名文件输出到同一个 --output-dir 时报告冲突，不覆盖先生成的文档
+#[test]
+fn colliding_outputs_are_reported() {
+    let root = fixture("collision");
+    for dir in ["a", "b"] {
+        fs::create_dir_all(root.join(dir)).unwrap();
+        fs::write(root.join(dir).join("util.lua"), format!("--- @brief {}\nfunction {}() end\n", dir, dir)).unwrap();
+    }
+
+    let output = run(&root, &["--files", "a/util.lua", "b/util.lua", "--output-dir", "out"]);
+    assert!(!output.status.success());
+    assert!(String::from_utf8_lossy(&output.stderr).contains("输出路径冲突"));
+    let written = fs::read_to_string(root.join("out").join("util.md")).unwrap();
+    assert!(written.contains("### a"));
+    assert!(!written.contains("### b"));
+    fs::remove_dir_all(&root).unwrap();
+}
+
+/// --include-dirs 时，源文件目录与指定目录中都找不到的本地头文件产生警告，`<...>` 系统头文件不检查
+#[test]
+fn missing_local_include_is_warned() {
+    let root = fixture("includes");
+    fs::create_dir_all(root.join("inc")).unwrap();
+    fs::write(root.join("inc").join("present.h"), "").unwrap();
+    fs::write(
+        root.join("a.c"),
+        "/**\n * @brief f\n * @includes <stdio.h>, \"present.h\", \"missing.h\"\n */\nint f(void);\n",
+    )
+    .unwrap();
+
+    let output = run(&root, &["--files", "a.c", "--include-dirs", "inc", "--dry-run"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("missing.h"), "{}", stderr);
+    assert!(!stderr.contains("present.h"));
+    assert!(!stderr.contains("stdio.h"));
+    fs::remove_dir_all(&root).unwrap();
+}
+
+/// --sort-params signature 按签名顺序重排打乱的 @param，未写文档与不在签名中的参数给出提示
+#[test]
+fn sort_params_follows_signature_order() {
+    let root = fixture("sort-params");
+    fs::write(
+        root.join("a.c"),
+        "/**\n * @brief f\n * @param c third\n * @param a first\n * @param stale removed\n */\nint f(int a, int b, int c);\n",
+    )
+    .unwrap();
+
+    let unsorted = run(&root, &["--files", "a.c", "--stdout"]);
+    assert!(String::from_utf8_lossy(&unsorted.stdout).contains("- c (int): third\n- a (int): first\n"));
+
+    let sorted = run(&root, &["--files", "a.c", "--stdout", "--sort-params", "signature"]);
+    let stdout = String::from_utf8_lossy(&sorted.stdout);
+    assert!(stdout.contains("- a (int): first\n- b\n- c (int): third\n- stale "), "{}", stdout);
+    let stderr = String::from_utf8_lossy(&sorted.stderr);
+    assert!(stderr.contains("参数 b 没有对应的 @param 文档"));
+    assert!(stderr.contains("@param stale 不在函数签名中"));
+    fs::remove_dir_all(&root).unwrap();
+}
+
+/// --log-format json 时每条警告是 stderr 上的一行 JSON 对象，正常输出仍在 stdout
+#[test]
+fn warnings_are_json_lines_on_stderr() {
+    let root = fixture("log-json");
+    fs::write(root.join("a.lua"), "--- @param x number\nfunction f(x) end\n").unwrap();
+
+    let output = run(&root, &["--files", "a.lua", "--log-format", "json", "--stdout"]);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    let lines: Vec<serde_json::Value> = stderr
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap_or_else(|e| panic!("{:?} 不是 JSON: {}", line, e)))
+        .collect();
+    assert_eq!(lines.len(), 1, "{}", stderr);
+    assert_eq!(lines[0]["level"], "warning");
+    assert_eq!(lines[0]["file"], "a.lua");
+    assert_eq!(lines[0]["line"], 1);
+    assert_eq!(lines[0]["code"], "missing-brief");
+    assert!(String::from_utf8_lossy(&output.stdout).contains("### f"));
+
+    // --fail-on-warning 的汇总同样是一行 JSON
+    let output = run(&root, &["--files", "a.lua", "--log-format", "json", "--fail-on-warning", "--stdout"]);
+    assert!(!output.status.success());
+    assert_eq!(json_codes(&output), ["missing-brief", "fail-on-warning"]);
+    fs::remove_dir_all(&root).unwrap();
+}
+
+/// stderr 上每一行 JSON 诊断信息的 code
+fn json_codes(output: &Output) -> Vec<String> {
+    String::from_utf8_lossy(&output.stderr)
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap_or_else(|e| panic!("{:?} 不是 JSON: {}", line, e)))
+        .map(|line| line["code"].as_str().unwrap_or_default().to_string())
+        .collect()
+}
+
+/// 扫描目录时因符号链接成环跳过的目录作为诊断信息输出，计入 --fail-on-warning
+#[cfg(unix)]
+#[test]
+fn scan_warnings_are_diagnostics() {
+    let root = fixture("scan-loop");
+    fs::write(root.join("a.lua"), "--- @brief f\nfunction f() end\n").unwrap();
+    fs::create_dir_all(root.join("sub")).unwrap();
+    std::os::unix::fs::symlink("..", root.join("sub").join("loop")).unwrap();
+
+    let output = run(&root, &["--all", "--recursive", "--log-format", "json", "--fail-on-warning", "--stdout"]);
+    assert!(!output.status.success());
+    assert_eq!(json_codes(&output), ["scan-skipped", "fail-on-warning"]);
+    fs::remove_dir_all(&root).unwrap();
+}
+
+/// --merge --append 把第二次运行的文档追加到第一次的输出之后，文档标题只出现一次
+#[test]
+fn append_accumulates_merged_runs() {
+    let root = fixture("append");
+    fs::write(root.join("a.lua"), "--- @brief a\nfunction a() end\n").unwrap();
+    fs::write(root.join("b.lua"), "--- @brief b\nfunction b() end\n").unwrap();
+
+    assert!(run(&root, &["--files", "a.lua", "--merge", "api.md"]).status.success());
+    let first = fs::read_to_string(root.join("api.md")).unwrap();
+    assert!(run(&root, &["--files", "b.lua", "--merge", "api.md", "--append"]).status.success());
+    let merged = fs::read_to_string(root.join("api.md")).unwrap();
+    assert!(merged.starts_with(&first));
+    assert!(merged[first.len()..].contains("## b.lua"));
+    assert_eq!(merged.matches("# api\n").count(), 1);
+
+    // 不加 --append 时覆盖已有文件
+    assert!(run(&root, &["--files", "b.lua", "--merge", "api.md"]).status.success());
+    assert!(!fs::read_to_string(root.join("api.md")).unwrap().contains("## a.lua"));
+    fs::remove_dir_all(&root).unwrap();
+}
+
+/// --fail-on-warning 时只要有一条警告就以非零状态码退出，警告仍然全部输出
+#[test]
+fn fail_on_warning_exits_non_zero() {
+    let root = fixture("fail-on-warning");
+    fs::write(root.join("a.lua"), "--- @param x number\nfunction f(x) end\n").unwrap();
+
+    assert!(run(&root, &["--files", "a.lua"]).status.success());
+    let output = run(&root, &["--files", "a.lua", "--fail-on-warning"]);
+    assert!(!output.status.success());
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("缺少简介"), "{}", stderr);
+    assert!(stderr.contains("--fail-on-warning"));
+
+    fs::write(root.join("a.lua"), "--- @brief f\n--- @param x number 输入\nfunction f(x) end\n").unwrap();
+    assert!(run(&root, &["--files", "a.lua", "--fail-on-warning"]).status.success());
+    fs::remove_dir_all(&root).unwrap();
+}
@@ -0,0 +1,46 @@
+//! `@example-file`：从外部示例文件读取示例代码
+
+use std::fs;
+use std::path::PathBuf;
+use todocument::{document_file, load_examples, render_markdown, LanguageOverrides};
+
+fn fixture(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("todocument-examples-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("examples")).unwrap();
+    fs::write(
+        root.join("math.lua"),
+        "--- @brief 两数相加\n--- @example-file add\nfunction add(a, b) end\n\n\
+         --- @brief 两数相减\n--- @example-file sub\nfunction sub(a, b) end\n",
+    )
+    .unwrap();
+    root
+}
+
+/// 默认在源文件旁的 examples/ 中查找，并尝试追加源文件的扩展名；找不到的文件名被返回
+#[test]
+fn loads_example_from_default_dir() {
+    let root = fixture("default");
+    fs::write(root.join("examples").join("add.lua"), "print(add(1, 2))").unwrap();
+
+    let mut doc = document_file(&root.join("math.lua"), &LanguageOverrides::default()).expect("解析失败");
+    assert_eq!(load_examples(&mut doc, None), vec!["sub".to_string()]);
+    assert_eq!(doc.blocks[0].example.as_deref(), Some("print(add(1, 2))"));
+    let markdown = render_markdown(&doc.blocks);
+    assert!(markdown.contains("```lua\nprint(add(1, 2))\n```"), "{}", markdown);
+    fs::remove_dir_all(&root).unwrap();
+}
+
+/// 指定的示例目录优先于默认的 examples/
+#[test]
+fn examples_dir_overrides_default() {
+    let root = fixture("custom");
+    fs::create_dir_all(root.join("snippets")).unwrap();
+    fs::write(root.join("examples").join("add.lua"), "default").unwrap();
+    fs::write(root.join("snippets").join("add"), "custom").unwrap();
+
+    let mut doc = document_file(&root.join("math.lua"), &LanguageOverrides::default()).expect("解析失败");
+    load_examples(&mut doc, Some(&root.join("snippets")));
+    assert_eq!(doc.blocks[0].example.as_deref(), Some("custom"));
+    fs::remove_dir_all(&root).unwrap();
+}
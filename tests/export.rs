@@ -0,0 +1,78 @@
+//! 汇总导出：参数矩阵 CSV、包含关系图与待办报告等跨文件的输出
+
+use std::path::PathBuf;
+use todocument::file_parser::{include_graph, params_to_csv, todo_report, CFileParser, FileParser, LuaFileParser};
+use todocument::{FileDoc, InputFileType};
+
+fn lua_doc(path: &str, source: &str) -> FileDoc {
+    FileDoc {
+        path: PathBuf::from(path),
+        lang: InputFileType::Lua,
+        blocks: LuaFileParser {}.parse_str(source),
+    }
+}
+
+fn c_doc(path: &str, source: &str) -> FileDoc {
+    FileDoc {
+        path: PathBuf::from(path),
+        lang: InputFileType::C,
+        blocks: CFileParser {}.parse_str(source),
+    }
+}
+
+/// 含逗号与引号的描述整体加引号，内部的引号按 CSV 规则双写；optional 列来自 `name?`
+#[test]
+fn params_csv_quotes_commas_and_quotes() {
+    let doc = lua_doc(
+        "a.lua",
+        "--- @brief f\n--- @param x number 横坐标, 单位为 \"px\"\n--- @param y? number 纵坐标\nfunction f(x, y) end\n",
+    );
+    let csv = params_to_csv(&[doc]).expect("导出失败");
+    assert_eq!(
+        csv,
+        "file,function,param,type,optional,description\n\
+         a.lua,f,x,number,false,\"横坐标, 单位为 \"\"px\"\"\"\n\
+         a.lua,f,y,number,true,纵坐标\n"
+    );
+}
+
+/// 两个文件包含同一个头文件：节点与边按名字排序，`"x.h"` 去掉引号，`<x.h>` 保持原样
+#[test]
+fn include_graph_is_valid_dot() {
+    let app = c_doc("src/app.c", "/**\n * @brief main\n * @includes \"util.h\", <stdio.h>\n */\nint main(void);\n");
+    let util = c_doc("src/util.c", "/**\n * @brief helper\n * @includes \"util.h\"\n */\nint helper(void);\n");
+    let expected = r#"digraph includes {
+    "<stdio.h>";
+    "src/app.c";
+    "src/util.c";
+    "util.h";
+    "src/app.c" -> "<stdio.h>";
+    "src/app.c" -> "util.h";
+    "src/util.c" -> "util.h";
+}
+"#;
+    assert_eq!(include_graph(&[app, util]), expected);
+}
+
+/// 节点名中的引号与反斜杠被转义
+#[test]
+fn include_graph_escapes_node_names() {
+    let source = "/**\n * @brief f\n * @includes \"util.h\"\n */\nint f(void);\n";
+    let dot = include_graph(&[c_doc(r#"src\my "lib".c"#, source)]);
+    assert!(dot.contains(r#"    "src\\my \"lib\".c" -> "util.h";"#), "{}", dot);
+}
+
+/// 待办报告列出每个 @todo 及其所在的文件与函数，没有待办时注明“无”
+#[test]
+fn todo_report_lists_todos_from_two_functions() {
+    let a = lua_doc("a.lua", "--- @brief f\n--- @todo 处理空列表\n--- @todo 支持负数\nfunction f() end\n");
+    let b = lua_doc("b.lua", "--- @brief g\nfunction g() end\n\n--- @brief h\n--- @todo 补充示例\nfunction M.h() end\n");
+    assert_eq!(
+        todo_report(&[a, b]),
+        "# TODO\n\n\
+         - `a.lua` · `f`: 处理空列表\n\
+         - `a.lua` · `f`: 支持负数\n\
+         - `b.lua` · `M.h`: 补充示例\n"
+    );
+    assert_eq!(todo_report(&[lua_doc("c.lua", "--- @brief g\nfunction g() end\n")]), "# TODO\n\n(无)\n");
+}
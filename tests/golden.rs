@@ -0,0 +1,158 @@
+//! 黄金文件测试：tests/golden/ 下的每个源文件经完整的解析+格式化流程后，
+//! 输出必须与同名的 `<文件名>.md` 逐字节一致
+//!
+//! 修改了输出格式后，用 `UPDATE_GOLDEN=1 cargo test --test golden` 重新生成期望文件
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use todocument::file_parser::{MarkdownFormatter, OutputFileFormatter};
+use todocument::json::JsonFormatter;
+use todocument::{create_file_parser, document_file, generate, render_markdown, InputFileType, LanguageOverrides};
+
+fn golden_dir() -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR")).join("tests").join("golden")
+}
+
+/// 按文件名排序的输入文件（除 .md 以外的所有文件）
+fn golden_inputs() -> Vec<PathBuf> {
+    let mut inputs: Vec<PathBuf> = fs::read_dir(golden_dir())
+        .expect("无法读取 tests/golden")
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.is_file() && p.extension().is_some_and(|e| e != "md"))
+        .collect();
+    inputs.sort();
+    inputs
+}
+
+/// 期望文件包含私有函数，以覆盖 local 等徽章的渲染
+fn render(path: &Path) -> String {
+    let doc = document_file(path, &LanguageOverrides::default()).expect("解析失败");
+    let formatter = MarkdownFormatter { include_private: true, ..MarkdownFormatter::default() };
+    generate(&doc.path, &doc.blocks, &formatter, None).expect("格式化失败")
+}
+
+#[test]
+fn golden_files() {
+    let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+    let inputs = golden_inputs();
+    assert!(!inputs.is_empty(), "tests/golden 中没有测试用例");
+
+    let mut failures = Vec::new();
+    for input in inputs {
+        let mut expected_path = input.clone().into_os_string();
+        expected_path.push(".md");
+        let expected_path = PathBuf::from(expected_path);
+        let actual = render(&input);
+
+        if update {
+            fs::write(&expected_path, &actual).expect("无法写入期望文件");
+            continue;
+        }
+        match fs::read_to_string(&expected_path) {
+            Ok(expected) if expected == actual => {}
+            Ok(expected) => failures.push(format!(
+                "{} 的输出与期望不一致\n--- 期望 ---\n{}\n--- 实际 ---\n{}",
+                input.display(),
+                expected,
+                actual
+            )),
+            Err(_) => failures.push(format!("缺少期望文件 {}", expected_path.display())),
+        }
+    }
+    assert!(failures.is_empty(), "{}", failures.join("\n\n"));
+}
+
+/// 从内存字符串解析与从文件解析的结果一致
+#[test]
+fn parse_str_matches_parse() {
+    for input in golden_inputs() {
+        let parser = create_file_parser(&InputFileType::from_path(&input));
+        let from_file = parser.parse(&fs::File::open(&input).expect("无法打开测试文件"));
+        let from_str = parser.parse_str(&fs::read_to_string(&input).expect("无法读取测试文件"));
+        assert_eq!(render_markdown(&from_file), render_markdown(&from_str), "{}", input.display());
+    }
+}
+
+/// 默认设置下私有函数（local 函数）不出现在输出中
+#[test]
+fn private_functions_are_omitted_by_default() {
+    let doc = document_file(&golden_dir().join("method.lua"), &LanguageOverrides::default()).expect("解析失败");
+    assert!(doc.blocks.iter().any(|b| b.is_local));
+    let markdown = render_markdown(&doc.blocks);
+    assert!(markdown.contains("Stack:push"));
+    assert!(!markdown.contains("check"));
+}
+
+/// JSON 输出与 Markdown 一样，只有指定 include_private 时才包含私有函数
+#[test]
+fn json_omits_private_functions_by_default() {
+    let doc = document_file(&golden_dir().join("method.lua"), &LanguageOverrides::default()).expect("解析失败");
+    let public = JsonFormatter::default().format(&doc.blocks).expect("格式化失败");
+    assert!(public.contains("Stack:push"));
+    assert!(!public.contains("check"));
+    let all = JsonFormatter { include_private: true }.format(&doc.blocks).expect("格式化失败");
+    assert!(all.contains("check"));
+}
+
+/// 包在反引号中的类型在结构化输出中不带反引号
+#[test]
+fn json_types_have_no_backticks() {
+    let doc = document_file(&golden_dir().join("backtick_types.lua"), &LanguageOverrides::default()).expect("解析失败");
+    let json = JsonFormatter::default().format(&doc.blocks).expect("格式化失败");
+    assert!(json.contains("\"type_name\": \"number\""));
+    assert!(json.contains("\"type_name\": \"table\""));
+    assert!(!json.contains('`'), "{}", json);
+}
+
+/// 返回值按自己的顺序编号，与参数的编号无关
+#[test]
+fn returns_are_numbered_in_order() {
+    let doc = document_file(&golden_dir().join("multi_return.lua"), &LanguageOverrides::default()).expect("解析失败");
+    let numbers: Vec<usize> = doc.blocks[0].ret_value.iter().map(|r| r.number).collect();
+    assert_eq!(numbers, vec![0, 1]);
+}
+
+/// CRLF 换行的 Lua 源码与 LF 换行的解析结果一致（BufRead::lines 会连同 \r 一起去掉行尾）
+#[test]
+fn lua_crlf_matches_lf() {
+    for input in golden_inputs().into_iter().filter(|p| p.extension().is_some_and(|e| e == "lua")) {
+        let parser = create_file_parser(&InputFileType::from_path(&input));
+        let lf = fs::read_to_string(&input).expect("无法读取测试文件");
+        let crlf = lf.replace('\n', "\r\n");
+        assert_eq!(
+            render_markdown(&parser.parse_str(&lf)),
+            render_markdown(&parser.parse_str(&crlf)),
+            "{}",
+            input.display()
+        );
+    }
+}
+
+/// 索引的每一项是签名与简介，链接到完整文档中同一函数的锚点
+#[test]
+fn index_links_to_block_anchors() {
+    let doc = document_file(&golden_dir().join("method.lua"), &LanguageOverrides::default()).expect("解析失败");
+    let formatter = MarkdownFormatter::default();
+    let index = formatter.format_index(&doc.blocks, "method.md");
+    let full = generate(&doc.path, &doc.blocks, &formatter, None).expect("格式化失败");
+    assert!(index.contains("- [`function Stack:push(value)`](method.md#stackpush)"));
+    assert!(full.contains("(#stackpush)"));
+    assert!(!index.contains("check"));
+}
+
+/// 后处理钩子看到的是 format_file 的完整输出，包括 front-matter
+#[test]
+fn post_process_sees_front_matter() {
+    let doc = document_file(&golden_dir().join("method.lua"), &LanguageOverrides::default()).expect("解析失败");
+    let formatter = MarkdownFormatter { front_matter: true, ..MarkdownFormatter::default() };
+    let mut seen = String::new();
+    let mut hook = |content: String| {
+        seen = content.clone();
+        content.replace("Stack", "Queue")
+    };
+    let output = generate(&doc.path, &doc.blocks, &formatter, Some(&mut hook)).expect("格式化失败");
+    assert!(seen.starts_with("---\ntitle: \"method\"\n"), "{}", seen);
+    assert!(output.starts_with("---\ntitle: \"method\"\n"));
+    assert!(!output.contains("Stack"));
+}
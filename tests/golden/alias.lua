@@ -0,0 +1,14 @@
+--- @brief 连接列表中的字符串
+--- @param list table 字符串列表
+--- @param sep string 分隔符
+--- @alias join
+--- @alias implode
+--- @return string 连接后的字符串
+function concat(list, sep)
+end
+
+--- @brief 按分隔符拆分字符串
+--- @param s string 输入字符串
+--- @see join
+function split(s)
+end
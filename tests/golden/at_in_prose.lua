@@ -0,0 +1,6 @@
+--- @brief 发送通知邮件
+--- @param to string 收件人，例如 admin@example.com
+---   有问题请 email me @host about @param
+--- @description
+---   \text 路径中的 C:\temp 不是子标签
+function notify(to) end
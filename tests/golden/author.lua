@@ -0,0 +1,14 @@
+--- @brief 字符串工具
+--- @author Jane
+--- @author Bob
+--- @version 1.2.0
+local M = {}
+
+--- @brief 去掉首尾空白
+--- @param s string 输入
+--- @return string 结果
+function M.trim(s)
+    return (s:gsub("^%s+", ""):gsub("%s+$", ""))
+end
+
+return M
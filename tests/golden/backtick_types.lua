@@ -0,0 +1,6 @@
+--- @brief 缩放向量
+--- @param v `table` 向量
+--- @param `factor` `number` 倍数
+--- @return `table` 缩放后的新向量
+function scale(v, factor)
+end
@@ -0,0 +1,8 @@
+--- @brief 在后台排序
+--- @param list table 待排序的列表
+--- @since 2.0
+--- @async
+--- @deprecated 请改用 sort
+--- @complexity O(n log n)
+local function sort_async(list)
+end
@@ -0,0 +1,30 @@
+local M = {}
+
+---------- Section: Networking ----------
+
+--- @brief 建立连接
+--- @param host string 主机名
+function M.connect(host)
+end
+
+--- @brief 断开连接
+function M.close()
+end
+
+-------------------- 存储 --------------------
+
+--- @brief 保存数据
+--- @param key string 键
+function M.save(key)
+end
+
+--- @brief 显式分组优先于分隔注释
+--- @group 工具
+function M.dump()
+end
+
+------------------------------------------------
+
+--- @brief 纯分隔线不开始新的分组
+function M.load()
+end
@@ -0,0 +1,25 @@
+--[[
+  @brief 合并两个表
+  @param a table 目标表
+  @param b table 来源表
+  @return table 合并后的 a
+]]
+function merge(a, b)
+end
+
+--[==[@brief 深拷贝一个表
+  @param t table 要拷贝的表
+  @description
+    \code local copy = deep_copy({ x = { [1] = "]]" } })
+]==]
+function deep_copy(t)
+end
+
+--[[
+function old_merge(a, b)
+end
+]]
+
+--[[@brief 单行块注释]]
+function noop()
+end
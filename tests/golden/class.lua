@@ -0,0 +1,24 @@
+--- @class Vector
+--- @brief 二维向量
+--- @field x number 横坐标
+--- @field y number 纵坐标
+local Vector = {}
+
+--- @brief 计算两点距离
+--- @param a Vector 起点
+--- @param b Vector 终点
+--- @return number 距离
+local function distance(a, b)
+end
+
+--- @brief 创建向量
+--- @param x number 横坐标
+--- @param y number 纵坐标
+--- @return Vector 新向量
+function Vector.new(x, y)
+end
+
+--- @brief 向量长度
+--- @return number 长度
+function Vector:len()
+end
@@ -0,0 +1,11 @@
+/**
+ * @brief Clamp a value into a range
+ * @param v value to clamp
+ * @param lo lower bound
+ * @param hi upper bound
+ * @return the clamped value
+ * @description
+ *   \text Values outside the range are pinned to the nearest bound.
+ *   \code int v = clamp(12, 0, 10);
+ */
+int clamp(int v, int lo, int hi);
@@ -0,0 +1,10 @@
+--- @brief 把值限制在区间内
+--- @param v number 输入值
+--- @param lo number 下界
+--- @param hi number 上界
+--- @return number 限制后的值
+--- @description
+---   \code local x = clamp(12, 0, 10)
+---   \code{text} clamp(v, lo, hi) = min(max(v, lo), hi)
+function clamp(v, lo, hi)
+end
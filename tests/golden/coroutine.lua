@@ -0,0 +1,10 @@
+--- @brief 逐个产生列表中的元素
+--- @param list table 输入列表
+--- @yields number 当前元素的下标
+--- @yields any 当前元素
+--- @return nil 遍历结束
+function iterate(list)
+    for i, v in ipairs(list) do
+        coroutine.yield(i, v)
+    end
+end
@@ -0,0 +1,10 @@
+--- @brief 旧的读取接口
+--- @deprecated 请改用 read_all
+--- @param path string 文件路径
+function read(path)
+end
+
+--- @brief 旧的写入接口
+--- @deprecated
+function write(path, data)
+end
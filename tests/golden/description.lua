@@ -0,0 +1,28 @@
+--- @brief 计算距离
+--- @param p table 点，包含：
+---   \list x: 横坐标
+---   \list y: 纵坐标
+--- @return number 到原点的距离
+--- @description
+---   \text 使用欧几里得距离
+---   \formula \sqrt{x^2 + y^2}
+---   \code print(distance({ x = 3, y = 4 }))
+---   \list 结果总是非负数
+function distance(p)
+    return math.sqrt(p.x * p.x + p.y * p.y)
+end
+
+--- @brief 调用外部脚本
+--- @description
+---   \code{python} print("hello")
+---   \code{unknown} print("fallback")
+function run_script()
+end
+
+--- @brief 质能方程
+--- @description
+---   \formula{block} E = mc^2
+---   \formula m
+---   \formula{centered} c
+function energy(m)
+end
@@ -0,0 +1,9 @@
+/**
+ * @brief Copy n bytes from src to dst
+ * @param[out] dst destination buffer
+ * @param[in] src source buffer
+ * @param[in,out] n bytes to copy, set to the number copied
+ * @param[ in , out ] status optional status word
+ * @return 0 on success
+ */
+int copy_bytes(char *dst, const char *src, size_t *n, int *status);
@@ -0,0 +1,6 @@
+--- @brief 读取配置文件
+--- @param path string 文件路径
+--- @return table 配置
+--- @throws IOError 文件不存在或无法读取时
+--- @error 配置格式不正确时调用 error("invalid config")
+function load_config(path) end
@@ -0,0 +1,10 @@
+--- @brief returns a*b for all _x_
+--- @param a number the [left] operand
+--- @param b number must be <= 10
+--- @return number a*b
+--- @description
+---   \text uses `*` internally
+---   \text call `init()` before use, ``a`b`` keeps its inner tick, a lone ` is escaped
+---   \code return a*b
+---   \formula a_1 * b_1
+function mul(a, b) end
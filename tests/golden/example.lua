@@ -0,0 +1,11 @@
+--- @brief 按条件过滤列表
+--- @param list table 输入列表
+--- @param pred function 谓词
+--- @example
+---   local evens = filter({ 1, 2, 3, 4 }, function(x)
+---       return x % 2 == 0  -- 保留偶数 @keep
+---   end)
+---
+--- @return table 过滤后的新列表
+function filter(list, pred)
+end
@@ -0,0 +1,10 @@
+--- @brief 服务器配置
+--- @field host string 监听的主机名
+--- @field port integer 监听端口
+--- @field tls boolean 是否启用 TLS
+local Config = {}
+
+--- @brief 用默认值创建配置
+--- @return Config 新的配置
+function Config.new()
+end
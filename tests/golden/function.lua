@@ -0,0 +1,12 @@
+--- @brief 两数相加
+--- @param a number 第一个数
+--- @param b number 第二个数
+--- @return number 两数之和
+--- @since 1.2
+function add(a, b)
+    return a + b
+end
+
+--- @brief 局部辅助函数
+--- @param s string 输入
+local function trim(s) return s end
@@ -0,0 +1,30 @@
+#include <string.h>
+
+/* plain
+   comment */
+
+/**
+ * @brief Add two ints
+ * @param a first
+ * @param b second
+ * @return the sum
+ */
+int add(int a, int b) { return a + b; }
+
+/// Duplicate a string.
+/// @param s source string
+static char *dup(const char *s);
+
+/** @brief Long prototype
+ *  @param buf output buffer
+ *  @param n size
+ */
+size_t
+fill(char *buf,
+     size_t n,
+     ...)
+{
+    return 0;
+}
+
+int undocumented(void);
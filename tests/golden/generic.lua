@@ -0,0 +1,7 @@
+--- @brief 对列表中的每个元素调用 fn，返回结果组成的新列表
+--- @generic T, U
+--- @generic K : table
+--- @param list T[] 输入列表
+--- @param fn fun(item: T): U 映射函数
+--- @return U[] 映射后的列表
+function map(list, fn) end
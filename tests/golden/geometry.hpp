@@ -0,0 +1,88 @@
+#pragma once
+#include <vector>
+
+namespace geo {
+
+/// 颜色
+enum class Color : unsigned char {
+    Red,   ///< 红
+    Green, ///< 绿
+};
+
+/**
+ * 所有形状的基类
+ */
+class Shape {
+public:
+    /// 构造一个形状
+    /// @param name 形状名
+    explicit Shape(const std::string &name);
+
+    /// 销毁形状
+    virtual ~Shape();
+
+    /// 计算面积
+    /// @return 面积
+    /// @description
+    ///   \text 由子类实现
+    ///   \code double a = shape.area();
+    virtual double area() const = 0;
+
+    /// 形状名
+    std::string name;
+
+    /// 比较两个形状
+    /// @param other 另一个形状
+    bool operator==(const Shape &other) const;
+
+protected:
+    /// 供子类使用的缩放
+    /// @param factor 倍数
+    void scale(double factor = 1.0) {
+        size_ *= factor;
+    }
+
+private:
+    /// 内部校验
+    bool check() const;
+    double size_;
+};
+
+/// 在容器中查找最大值
+/// @tparam T 元素类型
+/// @param items 元素列表
+/// @return 最大的元素
+template <typename T, int N = 4>
+T max_of(const std::vector<T> &items);
+
+/// 二维点
+struct Point
+{
+    int x, y; ///< 坐标
+    /// 附加属性
+    std::map<std::string, int> tags;
+
+    /// 到原点的距离
+    double norm() const;
+};
+
+namespace detail {
+/// 工具函数
+int helper(int x);
+}
+
+} // namespace geo
+
+/// 矩形面积
+/// @return 面积
+double geo::Rect::area() const {
+    return w * h;
+}
+
+namespace {
+/// 本文件内部使用
+static int internal() { return 1; }
+}
+
+/// 尾置返回类型
+auto twice(int x) -> int;
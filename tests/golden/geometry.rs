@@ -0,0 +1,55 @@
+//! Utilities for geometry.
+//!
+//! More module text.
+
+use std::fmt;
+
+/// A point in 2D.
+#[derive(Debug, Clone)]
+pub struct Point {
+    pub x: f64,
+}
+
+/// Computes the distance between two points.
+///
+/// Uses the Euclidean metric.
+///
+/// # Arguments
+///
+/// * `a` - the first point
+/// * `b` - the second point,
+///   which may equal `a`
+///
+/// # Returns
+///
+/// The non-negative distance.
+///
+/// # Examples
+///
+/// ```
+/// let d = distance(&a, &b);
+/// ```
+///
+/// # Panics
+///
+/// Never.
+pub fn distance<T: Into<f64>, U>(a: &Point, b: &Point) -> f64 {
+    0.0
+}
+
+/// Async helper
+async fn fetch(
+    url: &str,
+    retries: u32,
+) -> Result<String, Error>
+where
+    Error: fmt::Debug,
+{
+}
+
+impl Point {
+    /// Creates a point.
+    pub fn new(x: f64) -> Self { Point { x } }
+    /// Length.
+    pub fn len(&self) -> f64 { self.x }
+}
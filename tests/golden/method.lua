@@ -0,0 +1,20 @@
+local Stack = {}
+Stack.__index = Stack
+
+--- @brief 创建一个空栈
+--- @return Stack 新的栈
+function Stack.new()
+    return setmetatable({ items = {} }, Stack)
+end
+
+--- @brief 压入一个元素
+--- @param value any 要压入的值
+function Stack:push(value)
+    table.insert(self.items, value)
+end
+
+--- @brief 内部校验
+local function check(stack)
+end
+
+return Stack
@@ -0,0 +1,9 @@
+local cache = {}
+
+--- @brief 按名字加载资源，结果会被缓存
+--- @param name string 资源名
+--- @return table 资源
+return function(name)
+    cache[name] = cache[name] or { name = name }
+    return cache[name]
+end
@@ -0,0 +1,31 @@
+local M = {}
+M.util = {}
+
+local mt = { __index = M }
+
+--- @brief 创建一个计数器
+--- @param start number 初始值
+--- @return table 计数器
+function M.new(start)
+    return setmetatable({ value = start }, mt)
+end
+
+--- @brief 计数器加一
+function M:inc()
+    self.value = self.value + 1
+end
+
+--- @brief 把数值限制在非负范围内
+--- @param n number 输入值
+--- @return number 非负的值
+function M.util.clamp(n)
+    return math.max(n, 0)
+end
+
+--- @brief 内部使用的校验
+--- @param c table 计数器
+local function check(c)
+    assert(c.value)
+end
+
+return setmetatable(M, { __call = function(_, start) return M.new(start) end })
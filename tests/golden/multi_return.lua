@@ -0,0 +1,7 @@
+--- @brief 在列表中查找值
+--- @param list table 要查找的列表
+--- @param value any 要查找的值
+--- @return boolean 是否找到
+--- @return integer 找到时的下标，否则为 nil
+function find(list, value)
+end
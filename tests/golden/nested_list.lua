@@ -0,0 +1,24 @@
+--- @brief 打开连接
+--- @description
+---   \list 支持的选项：
+---   - timeout
+---     - connect
+---     - read
+---   - retries
+--- @param opts table 连接选项
+---   \list
+---   - host
+---       - 默认为 localhost
+function connect(opts)
+end
+
+--[[
+  @brief 关闭连接
+  @description
+    \list
+    - 正常关闭
+        - 等待未完成的请求
+    - 强制关闭
+]]
+function close()
+end
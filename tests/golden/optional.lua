@@ -0,0 +1,5 @@
+--- @brief 等待事件
+--- @param event string 事件名
+--- @param timeout? number 超时时间（秒）
+--- @return boolean 是否收到事件
+function wait(event, timeout) end
@@ -0,0 +1,8 @@
+--- @brief 多段描述
+--- @description 第一段的第一行
+--- 第一段的第二行
+---
+--- 第二段
+--
+---   \text 第三段
+function f() end
@@ -0,0 +1,14 @@
+--- @brief 打开窗口
+--- @param title string 窗口标题
+--- @param opts table 窗口选项，
+---   可以省略任意一项：
+---   \list width: 宽度，默认 800
+---   \list height: 高度，默认 600
+---   \list
+---   - mode: 显示模式
+---     - windowed
+---     - fullscreen
+---   \code open("demo", { width = 1024 })
+--- @return boolean 是否成功
+function open(title, opts)
+end
@@ -0,0 +1,13 @@
+--- @brief 复制文件
+--- @param src string 源路径
+-- TODO: 支持目录
+--- @param dst string 目标路径，
+---   已存在时会被覆盖
+function copy(src, dst)
+end
+
+-- @brief 移动文件
+-- @param src string 源路径
+-- 同一文件系统内只是重命名
+function move(src)
+end
@@ -0,0 +1,8 @@
+/**
+ * 二维点
+ */
+typedef struct {
+    int x; ///< 横坐标
+    /// 纵坐标
+    int y;
+} Point;
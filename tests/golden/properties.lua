@@ -0,0 +1,16 @@
+--- @class Sprite
+--- @brief 可绘制的精灵
+--- @property x number 横坐标，读写时经过 get_x/set_x
+--- @property y number 纵坐标
+--- @property visible boolean 是否可见
+local Sprite = {}
+
+--- @brief 读取横坐标
+--- @return number 横坐标
+function Sprite:get_x()
+end
+
+--- @brief 设置横坐标
+--- @param x number 新的横坐标
+function Sprite:set_x(x)
+end
@@ -0,0 +1,17 @@
+local Account = {}
+Account.__index = Account
+
+--- @brief 存入一笔钱
+--- @param amount number 金额
+function Account:deposit(amount)
+    self.balance = self.balance + amount
+end
+
+--- @brief 转账到另一个账户
+--- @param self Account 转出的账户
+--- @param target Account 转入的账户
+--- @param amount number 金额
+function Account:transfer(target, amount)
+end
+
+return Account
@@ -0,0 +1,29 @@
+#include <stdint.h>
+
+/**
+ * @brief A rectangle in screen coordinates
+ */
+struct Rect {
+    int x, y;         ///< top-left corner
+    /** width in pixels */
+    int width;
+    /**
+     * height in pixels
+     */
+    int height;
+    uint32_t flags;   /* not documented */
+};
+
+/// Pixel formats
+enum Format {
+    FORMAT_RGB = 0,   ///< 3 bytes per pixel
+    FORMAT_RGBA,      ///< 4 bytes per pixel
+    /// single channel
+    FORMAT_GRAY = 8,
+};
+
+/// A value that is either an int or a float
+union Number {
+    int i;    ///< integer view
+    float f;  ///< floating point view
+};
@@ -0,0 +1,14 @@
+local M = {}
+
+--- @brief 压入元素
+--- @param x any 元素
+--- @see M.pop
+--- @see table.insert
+function M.push(x) end
+
+--- @brief 弹出元素
+--- @return any 栈顶元素
+--- @see M.push
+function M.pop() end
+
+return M
@@ -0,0 +1,52 @@
+/**
+ * Compute the area of a circle.
+ *
+ * Uses the classic formula.
+ * @param {number} r - the radius
+ * @param {Object.<string, number>} [opts={}] extra options
+ * @returns {number} the area
+ * @throws {RangeError} when r is negative
+ */
+export function area(r, opts = {}) {
+  return Math.PI * r * r;
+}
+
+/**
+ * Add two numbers.
+ * @param {number} a first
+ * @param {number} b second
+ * @return {number}
+ */
+const add = (a, b) => a + b;
+
+/** A 2D point. */
+export class Point {
+  /**
+   * Create a point.
+   * @param {number} x
+   * @param {number} y
+   */
+  constructor(x, y) {
+    this.x = x;
+    this.y = y;
+  }
+
+  /**
+   * Distance to another point.
+   * @param {Point} other the other point
+   * @returns {number}
+   */
+  async distanceTo(other,
+                   unused) {
+    return 0;
+  }
+
+  /** @private */
+  #secret() {}
+}
+
+/**
+ * Not a member.
+ * @param {string} s input
+ */
+function shout(s) { return s.toUpperCase(); }
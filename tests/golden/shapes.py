@@ -0,0 +1,63 @@
+"""几何图形工具"""
+
+import math
+
+
+def area(radius: float, precise=False) -> float:
+    """计算圆的面积。
+
+    面积按 pi * r^2 计算。
+
+    Args:
+        radius: 半径，必须为非负数
+        precise (bool, optional): 是否使用高精度的 pi，
+            默认为 False
+
+    Returns:
+        圆的面积
+
+    Raises:
+        ValueError: 半径为负数时
+
+    Example:
+        >>> area(1.0)
+        3.14159
+    """
+    if radius < 0:
+        raise ValueError("negative radius")
+    return math.pi * radius * radius
+
+
+def undocumented(x):
+    return x
+
+
+class Circle(Shape):
+    """圆形。
+
+    Attributes:
+        radius (float): 半径
+    """
+
+    def __init__(self, radius):
+        self.radius = radius
+
+    def scale(self, factor: float) -> "Circle":
+        """按比例缩放。
+
+        :param factor: 缩放比例
+        :type factor: float
+        :returns: 新的圆
+        :raises ValueError: 比例不为正数时
+        """
+        return Circle(self.radius * factor)
+
+    async def fetch(
+        self,
+        url: str,
+    ) -> bytes:
+        '''下载数据'''
+
+
+def _helper():
+    """内部函数"""
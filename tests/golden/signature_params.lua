@@ -0,0 +1,4 @@
+--- @brief 在区间内截取字符串
+--- @param last integer 结束位置
+--- @param s string 原字符串
+function substr(s, first, last) end
@@ -0,0 +1,9 @@
+--- @brief 返回常量 1
+function f() return 1 end
+
+--- @brief 什么也不做
+function noop() end
+
+--- @brief 加倍
+--- @param x number 输入
+local function double(x) return x * 2 end
@@ -0,0 +1,16 @@
+local M = {
+    --[[@brief 两数相加
+        @param a number 第一个数
+        @param b number 第二个数
+        @return number 和]]
+    add = function(a, b)
+        return a + b
+    end,
+
+    --- @brief 默认精度
+    precision = 2,
+
+    undocumented = function() end,
+}
+
+return M
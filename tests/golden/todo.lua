@@ -0,0 +1,6 @@
+--- @brief 解析配置文件
+--- @param path string 文件路径
+--- @todo 支持 include 指令
+--- @todo 报告错误所在的行号
+function parse_config(path)
+end
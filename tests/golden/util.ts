@@ -0,0 +1,14 @@
+/**
+ * Find an item.
+ * @template T
+ * @param items the list
+ * @param pred predicate
+ */
+export function find<T>(items: T[], pred: (item: T) => boolean): T | undefined {
+  return items.find(pred);
+}
+
+/** Options for fetch. */
+export interface FetchOptions {
+  timeout: number;
+}
@@ -0,0 +1,16 @@
+--- @brief 格式化并输出日志
+--- @param level string 日志级别
+--- @param ... any 要输出的值
+function log(level, ...)
+end
+
+--- @brief 输出所有参数
+function print_all(...)
+end
+
+--- @brief 在字符串中匹配模式
+--- @param s string 输入字符串
+--- @param pattern string 匹配模式
+--- @return ... 匹配到的所有捕获
+function match_all(s, pattern)
+end
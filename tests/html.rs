@@ -0,0 +1,35 @@
+//! HTML 输出：转义与公式定界符
+
+use todocument::file_parser::{FileParser, LuaFileParser};
+use todocument::html::HtmlFormatter;
+use todocument::OutputFileFormatter;
+
+#[test]
+fn escapes_code_and_wraps_formulas() {
+    let source = "\
+--- @brief 比较 a < b
+--- @description
+---   \\formula a < b
+---   \\formula{block} E = mc^2
+--- @param a table<string, number> 左值
+function less(a, b) end
+";
+    let blocks = LuaFileParser {}.parse_str(source);
+    let html = HtmlFormatter::default().format(&blocks).unwrap();
+    assert!(html.starts_with("<!DOCTYPE html>"));
+    assert!(html.ends_with("</html>\n"));
+    assert!(html.contains("<pre><code class=\"language-lua\">function less(a, b)</code></pre>"));
+    assert!(html.contains("<p><strong>Brief:</strong> 比较 a &lt; b</p>"));
+    assert!(html.contains("<li><code>a</code> (<em>table&lt;string, number&gt;</em>): 左值</li>"));
+    assert!(html.contains("\\(a &lt; b\\)"));
+    assert!(html.contains("\\[E = mc^2\\]"));
+    assert!(html.contains("mathjax"));
+}
+
+/// 徽章行与 Markdown 输出一致：弃用标记与带前缀的复杂度
+#[test]
+fn badges_match_markdown() {
+    let source = "--- @brief f\n--- @deprecated\n--- @complexity O(n)\nfunction f() end\n";
+    let html = HtmlFormatter::default().format(&LuaFileParser {}.parse_str(source)).unwrap();
+    assert!(html.contains("<p class=\"badges\">deprecated · complexity O(n)</p>"), "{}", html);
+}
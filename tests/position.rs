@@ -0,0 +1,25 @@
+//! 文档块的位置：文档注释第一行与声明结束行的行号
+
+use todocument::file_parser::{FileParser, LuaFileParser};
+
+#[test]
+fn lua_blocks_record_line_range() {
+    let source = "\
+local M = {}
+
+--- @brief a
+--- @param x number
+function M.a(x,
+             y)
+end
+
+local T = {
+    --- @brief 字段
+    f = function() end,
+}
+";
+    let blocks = LuaFileParser {}.parse_str(source);
+    let ranges: Vec<(String, usize, usize)> =
+        blocks.iter().map(|b| (b.function_name(), b.start_line, b.end_line)).collect();
+    assert_eq!(ranges, vec![("M.a".to_string(), 3, 6), ("T.f".to_string(), 10, 11)]);
+}
@@ -0,0 +1,161 @@
+//! 目录扫描：.gitignore 与隐藏目录、--only / --exclude 过滤以及按路径覆盖语言
+
+use std::fs;
+use std::path::{Path, PathBuf};
+use todocument::{document_file, document_tree, InputFileType, LanguageOverrides, Options, ToDocError};
+
+/// 在临时目录下建立 src/、target/、.hidden/ 三个目录，各放一个 Lua 文件，并忽略 target/
+fn scan_fixture(name: &str) -> PathBuf {
+    let root = std::env::temp_dir().join(format!("todocument-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    for dir in ["src", "target", ".hidden"] {
+        fs::create_dir_all(root.join(dir)).unwrap();
+        fs::write(root.join(dir).join("a.lua"), "--- @brief f\nfunction f() end\n").unwrap();
+    }
+    fs::write(root.join(".gitignore"), "target/\n").unwrap();
+    root
+}
+
+fn scanned(root: &Path, no_ignore: bool) -> Vec<PathBuf> {
+    scanned_with(root, &Options { recursive: true, no_ignore, ..Options::default() })
+}
+
+/// 按 opts 扫描 root，返回排序后的相对路径
+fn scanned_with(root: &Path, opts: &Options) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = document_tree(root, opts)
+        .expect("扫描失败")
+        .docs
+        .into_iter()
+        .map(|doc| doc.path.strip_prefix(root).unwrap().to_path_buf())
+        .collect();
+    paths.sort();
+    paths
+}
+
+#[test]
+fn respects_gitignore_and_hidden_dirs() {
+    let root = scan_fixture("ignore");
+    assert_eq!(scanned(&root, false), vec![PathBuf::from("src/a.lua")]);
+    assert_eq!(
+        scanned(&root, true),
+        vec![PathBuf::from(".hidden/a.lua"), PathBuf::from("src/a.lua"), PathBuf::from("target/a.lua")]
+    );
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn exclude_patterns_match_relative_paths() {
+    let root = std::env::temp_dir().join(format!("todocument-exclude-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    for file in ["src/a.lua", "src/a_spec.lua", "test/b.lua", "src/test/c.lua"] {
+        let path = root.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "--- @brief f\nfunction f() end\n").unwrap();
+    }
+    let opts = Options {
+        recursive: true,
+        exclude: vec!["**/test/**".to_string(), "*_spec.lua".to_string()],
+        ..Options::default()
+    };
+    let paths: Vec<PathBuf> = document_tree(&root, &opts)
+        .expect("扫描失败")
+        .docs
+        .into_iter()
+        .map(|doc| doc.path.strip_prefix(&root).unwrap().to_path_buf())
+        .collect();
+    assert_eq!(paths, vec![PathBuf::from("src/a.lua")]);
+    fs::remove_dir_all(&root).unwrap();
+}
+
+#[test]
+fn results_are_sorted_by_path() {
+    let root = std::env::temp_dir().join(format!("todocument-order-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    for file in ["z.lua", "b/y.lua", "a.lua", "b/a.lua", "m.lua"] {
+        let path = root.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "--- @brief f\nfunction f() end\n").unwrap();
+    }
+    let opts = Options { recursive: true, ..Options::default() };
+    let paths: Vec<PathBuf> = document_tree(&root, &opts)
+        .expect("扫描失败")
+        .docs
+        .into_iter()
+        .map(|doc| doc.path.strip_prefix(&root).unwrap().to_path_buf())
+        .collect();
+    let expected = ["a.lua", "b/a.lua", "b/y.lua", "m.lua", "z.lua"];
+    assert_eq!(paths, expected.iter().map(PathBuf::from).collect::<Vec<_>>());
+    fs::remove_dir_all(&root).unwrap();
+}
+
+/// --only 只保留匹配的文件，--exclude 再从中去掉匹配的文件
+#[test]
+fn only_patterns_restrict_and_exclude_subtracts() {
+    let root = std::env::temp_dir().join(format!("todocument-only-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    for file in ["src/api/a.lua", "src/api/internal/b.lua", "src/core/c.lua", "d.lua"] {
+        let path = root.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, "--- @brief f\nfunction f() end\n").unwrap();
+    }
+    let only = Options { recursive: true, only: vec!["src/api/**".to_string()], ..Options::default() };
+    assert_eq!(
+        scanned_with(&root, &only),
+        vec![PathBuf::from("src/api/a.lua"), PathBuf::from("src/api/internal/b.lua")]
+    );
+    let both = Options { exclude: vec!["**/internal/**".to_string()], ..only };
+    assert_eq!(scanned_with(&root, &both), vec![PathBuf::from("src/api/a.lua")]);
+    fs::remove_dir_all(&root).unwrap();
+}
+
+/// --lang-for 规则按完整路径匹配：`.lua.in` 文件按 Lua 解析，不匹配的文件仍按扩展名识别
+#[test]
+fn lang_for_parses_templated_files() {
+    let root = std::env::temp_dir().join(format!("todocument-lang-for-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(root.join("gen")).unwrap();
+    fs::write(root.join("gen").join("config.lua.in"), "--- @brief 读取 @PREFIX@ 下的配置\nfunction load() end\n").unwrap();
+    fs::write(root.join("b.lua"), "--- @brief f\nfunction f() end\n").unwrap();
+
+    let input = root.join("gen").join("config.lua.in");
+    assert!(matches!(
+        document_file(&input, &LanguageOverrides::default()),
+        Err(ToDocError::UnsupportedLanguage(_))
+    ));
+    let overrides = LanguageOverrides::new(&["*.lua.in=lua".to_string()]).expect("规则无效");
+    let doc = document_file(&input, &overrides).expect("解析失败");
+    assert_eq!(doc.lang, InputFileType::Lua);
+    assert_eq!(doc.blocks[0].function_name(), "load");
+
+    let opts = Options { recursive: true, lang_for: vec!["*.lua.in=lua".to_string()], ..Options::default() };
+    assert_eq!(scanned_with(&root, &opts), vec![PathBuf::from("b.lua"), PathBuf::from("gen/config.lua.in")]);
+    fs::remove_dir_all(&root).unwrap();
+}
+
+/// 无法读取的文件记入 errors，其余文件照常解析
+#[cfg(unix)]
+#[test]
+fn unreadable_file_does_not_abort_scan() {
+    use std::os::unix::fs::PermissionsExt;
+
+    let root = std::env::temp_dir().join(format!("todocument-unreadable-{}", std::process::id()));
+    let _ = fs::remove_dir_all(&root);
+    fs::create_dir_all(&root).unwrap();
+    for file in ["a.lua", "b.lua", "c.lua"] {
+        fs::write(root.join(file), "--- @brief f\nfunction f() end\n").unwrap();
+    }
+    fs::set_permissions(root.join("b.lua"), fs::Permissions::from_mode(0o000)).unwrap();
+    // root 用户无视文件权限，无法构造读取失败的情形
+    if fs::File::open(root.join("b.lua")).is_ok() {
+        fs::remove_dir_all(&root).unwrap();
+        return;
+    }
+
+    let tree = document_tree(&root, &Options::default()).expect("扫描失败");
+    let paths: Vec<PathBuf> = tree.docs.iter().map(|doc| doc.path.strip_prefix(&root).unwrap().to_path_buf()).collect();
+    assert_eq!(paths, vec![PathBuf::from("a.lua"), PathBuf::from("c.lua")]);
+    assert_eq!(tree.errors.len(), 1);
+    assert_eq!(tree.errors[0].0, root.join("b.lua"));
+    assert!(matches!(tree.errors[0].1, ToDocError::Io(..)));
+    fs::remove_dir_all(&root).unwrap();
+}
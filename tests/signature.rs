@@ -0,0 +1,123 @@
+//! 签名解析的边界情况
+
+use todocument::file_parser::{CFileParser, FileParser, LuaFileParser, Signature};
+
+/// 不完整的签名（有 `.` 但没有括号）不会 panic，也不是成员函数
+#[test]
+fn member_function_without_parentheses() {
+    assert!(!LuaFileParser::is_member_function("function foo.bar", "foo"));
+    assert!(!LuaFileParser::is_member_function("function foo.bar(", "foo"));
+    assert!(LuaFileParser::is_member_function("function foo:bar", "foo"));
+    assert!(LuaFileParser::is_member_function("function foo.bar(foo, x)", "foo"));
+}
+
+/// 可变参数 `...` 不是 self 参数
+#[test]
+fn variadic_first_parameter_is_not_self() {
+    assert!(!LuaFileParser::is_member_function("function M.f(...)", "M"));
+    assert!(LuaFileParser::is_member_function("function M.f(M, ...)", "M"));
+}
+
+/// 行尾注释、空白与分号不影响判断声明是否在这一行结束
+#[test]
+fn api_tail_ignores_trailing_comment_and_semicolon() {
+    assert!(LuaFileParser::is_api_tail("function foo(a) -- comment"));
+    assert!(LuaFileParser::is_api_tail("function foo(a);"));
+    assert!(LuaFileParser::is_api_tail("function foo(a) ; "));
+    assert!(LuaFileParser::is_api_tail("function foo(a) return a end -- done"));
+    assert!(!LuaFileParser::is_api_tail("function foo(a, -- first"));
+    assert!(!LuaFileParser::is_api_tail("function send"));
+}
+
+/// 带行尾注释或分号的单行声明被识别为完整的函数
+#[test]
+fn single_line_declarations_with_comment_or_semicolon() {
+    let source = "\
+--- @brief foo
+function foo(a) -- does stuff
+
+--- @brief bar
+function bar(b);
+";
+    let blocks = LuaFileParser {}.parse_str(source);
+    let signatures: Vec<&str> = blocks.iter().map(|b| b.signature.raw.as_str()).collect();
+    assert_eq!(signatures, vec!["function foo(a)", "function bar(b)"]);
+}
+
+/// C++ 的限定名、运算符、构造/析构函数与尾置返回类型
+#[test]
+fn cpp_qualified_signatures() {
+    let sig = Signature::parse_cpp("template <typename T> static T ns::Box<T>::get(int i = 0) const").unwrap();
+    assert_eq!((sig.owner.as_str(), sig.name.as_str(), sig.ret.as_deref()), ("ns::Box<T>", "get", Some("T")));
+    assert_eq!(sig.params, vec!["i"]);
+
+    let sig = Signature::parse_cpp("bool Vec::operator<(const Vec &other) const").unwrap();
+    assert_eq!((sig.owner.as_str(), sig.name.as_str()), ("Vec", "operator<"));
+
+    let sig = Signature::parse_cpp("int operator()(int x)").unwrap();
+    assert_eq!((sig.name.as_str(), sig.params.clone()), ("operator()", vec!["x".to_string()]));
+
+    let sig = Signature::parse_cpp("virtual ~Shape()").unwrap();
+    assert_eq!((sig.name.as_str(), sig.ret), ("~Shape", None));
+
+    let sig = Signature::parse_cpp("auto twice(int x) -> int").unwrap();
+    assert_eq!(sig.ret.as_deref(), Some("int"));
+
+    let sig = Signature::parse_cpp("void each(std::function<void(int, int)> f, const std::map<K, V> &m)").unwrap();
+    assert_eq!(sig.params, vec!["f", "m"]);
+
+    assert!(Signature::parse_cpp("int x = compute(1)").is_none());
+}
+
+/// 去掉行尾注释时保留长字符串，跳过行内闭合的块注释
+#[test]
+fn remove_annotation_handles_long_brackets() {
+    assert_eq!(LuaFileParser::remove_annotation("local s = [==[a -- b]==] -- c"), "local s = [==[a -- b]==]");
+    assert_eq!(LuaFileParser::remove_annotation("f(x) --[[ skip ]] + 1"), "f(x)  + 1");
+    assert_eq!(LuaFileParser::remove_annotation("f(x) --[[ open"), "f(x)");
+    assert_eq!(LuaFileParser::remove_annotation("local s = '--' -- c"), "local s = '--'");
+}
+
+/// 按签名重排参数后，number 与参数在列表中的新位置一致
+#[test]
+fn params_are_renumbered_after_sorting() {
+    let source = "\
+/**
+ * @param b second
+ * @param extra not in the signature
+ * @param a first
+ */
+int f(int a, int b, int c);
+";
+    let mut blocks = CFileParser {}.parse_str(source);
+    let block = &mut blocks[0];
+    block.sort_params_by_signature();
+    let params: Vec<(&str, usize)> = block.parameters.iter().map(|p| (p.name.as_str(), p.number)).collect();
+    assert_eq!(params, vec![("a", 0), ("b", 1), ("c", 2), ("extra", 3)]);
+}
+
+/// 任意空格/制表符混合的缩进下，注释标记都被同样去掉
+#[test]
+fn comment_leader_ignores_mixed_indentation() {
+    for line in ["---@brief x", "\t--- @brief x", "  \t-- @brief x", "\t \t--@brief x", "    ---   @brief x\t"] {
+        assert_eq!(LuaFileParser::strip_lua_comment_leader(line), Some("@brief x"), "{:?}", line);
+    }
+    assert_eq!(LuaFileParser::strip_lua_comment_leader("\t  local x = 1"), None);
+    assert_eq!(LuaFileParser::strip_lua_comment_leader(" \t--"), Some(""));
+}
+
+/// 用制表符、空格或两者混合缩进的文档注释解析结果相同
+#[test]
+fn mixed_indentation_parses_identically() {
+    let spaces = "local M = {}\n    --- @brief f\n    --- @param x number 输入\n    function M.f(x) end\n";
+    let tabs = spaces.replace("    ", "\t");
+    let mixed = spaces.replace("    ", " \t ");
+    let parse = |s: &str| {
+        let blocks = LuaFileParser {}.parse_str(s);
+        blocks.iter().map(|b| (b.function_name(), b.brief.clone(), b.parameters.len())).collect::<Vec<_>>()
+    };
+    let expected = vec![("M.f".to_string(), "f".to_string(), 1)];
+    assert_eq!(parse(spaces), expected);
+    assert_eq!(parse(&tabs), expected);
+    assert_eq!(parse(&mixed), expected);
+}
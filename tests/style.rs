@@ -0,0 +1,65 @@
+//! MarkdownFormatter 的各种渲染风格
+
+use todocument::file_parser::{FileParser, LuaFileParser, MarkdownFormatter, OutputFileFormatter, Style};
+
+/// compact 风格：签名之后只有一行参数与返回值摘要，不输出完整的小节
+#[test]
+fn compact_style_summarizes_params_on_one_line() {
+    let source = "\
+--- @brief 两点之间的距离
+--- @param x number 横坐标
+--- @param y number 纵坐标
+--- @return number 距离
+function distance(x, y) end
+
+--- @brief 重置
+function reset() end
+";
+    let blocks = LuaFileParser {}.parse_str(source);
+    let formatter = MarkdownFormatter { style: Style::Compact, file_toc: false, ..MarkdownFormatter::default() };
+    let markdown = formatter.format(&blocks).expect("格式化失败");
+    assert_eq!(
+        markdown,
+        "```lua\nfunction distance(x, y)\n```\nparams: x:number, y:number → number\n\n两点之间的距离\n\n\
+         ```lua\nfunction reset()\n```\n重置\n\n"
+    );
+}
+
+/// 元信息合并为签名下方的一行徽章，badges 关闭时不输出
+#[test]
+fn badges_can_be_disabled() {
+    let source = "--- @brief f\n--- @since 2.0\n--- @async\nfunction f() end\n";
+    let blocks = LuaFileParser {}.parse_str(source);
+    let with = MarkdownFormatter::default().format(&blocks).expect("格式化失败");
+    assert!(with.contains("```\n🏷️ since 2.0 · async\n\n"), "{}", with);
+    let without = MarkdownFormatter { badges: false, ..MarkdownFormatter::default() }.format(&blocks).expect("格式化失败");
+    assert!(!without.contains("🏷️"));
+}
+
+/// heading_offset 把所有标题下移：分组的 H2 变为 H3，函数标题的 H3 变为 H4，最多到 H6
+#[test]
+fn heading_offset_shifts_every_heading() {
+    let source = "--- @brief f\n--- @group 数学\nfunction f() end\n\n--- @brief g\n--- @group 数学\nfunction g() end\n";
+    let blocks = LuaFileParser {}.parse_str(source);
+    let shifted = MarkdownFormatter { heading_offset: 1, ..MarkdownFormatter::default() }.format(&blocks).expect("格式化失败");
+    assert!(shifted.starts_with("### Contents\n\n"), "{}", shifted);
+    assert!(shifted.contains("\n### 数学\n\n#### f\n\n"), "{}", shifted);
+    assert!(!shifted.lines().any(|l| l.starts_with("## ")));
+
+    let clamped = MarkdownFormatter { heading_offset: 5, ..MarkdownFormatter::default() }.format(&blocks).expect("格式化失败");
+    assert!(clamped.contains("\n###### 数学\n\n###### f\n\n"), "{}", clamped);
+}
+
+/// 关闭签名后不输出代码块，以函数名标题代替；目录已输出标题时不重复
+#[test]
+fn no_signature_renders_name_heading() {
+    let blocks = LuaFileParser {}.parse_str("--- @brief 两数相加\nfunction M.add(a, b) end\n");
+    let formatter = MarkdownFormatter { signature: false, file_toc: false, ..MarkdownFormatter::default() };
+    let markdown = formatter.format(&blocks).expect("格式化失败");
+    assert!(markdown.starts_with("### M.add\n\n**Brief:** 两数相加\n"), "{}", markdown);
+    assert!(!markdown.contains("```"));
+
+    let with_toc = MarkdownFormatter { signature: false, ..MarkdownFormatter::default() }.format(&blocks).expect("格式化失败");
+    assert_eq!(with_toc.matches("### M.add").count(), 1, "{}", with_toc);
+    assert!(!with_toc.contains("```"));
+}
@@ -0,0 +1,81 @@
+//! tera 模板输出：模板目录中的 block / index 模板与内置格式化器的回退
+
+use std::fs;
+use std::path::PathBuf;
+use todocument::file_parser::{FileParser, LuaFileParser, MarkdownFormatter};
+use todocument::template::TemplateFormatter;
+use todocument::OutputFileFormatter;
+
+const SOURCE: &str = "\
+--- @brief 两数相加
+--- @param a number 第一个数
+--- @param b number 第二个数
+--- @return number 和
+function add(a, b) end
+";
+
+/// 建立一个只含给定模板的临时目录
+fn template_dir(name: &str, templates: &[(&str, &str)]) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("todocument-template-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    for (file, content) in templates {
+        fs::write(dir.join(file), content).unwrap();
+    }
+    dir
+}
+
+/// 自定义的 block 模板可以使用签名、简介、参数与返回值等变量，index 模板包裹所有文档块
+#[test]
+fn renders_custom_block_template() {
+    let dir = template_dir(
+        "block",
+        &[
+            (
+                TemplateFormatter::BLOCK_TEMPLATE,
+                "## {{ name }}\n`{{ signature }}` {{ brief }}\n{% for p in params %}* {{ p.number }}. {{ p.name }}: {{ p.type }}\n{% endfor %}{% for r in returns %}=> {{ r.type }} {{ r.description }}\n{% endfor %}",
+            ),
+            (TemplateFormatter::INDEX_TEMPLATE, "# API ({{ blocks | length }})\n{{ content }}"),
+        ],
+    );
+    let formatter = TemplateFormatter::new(&dir, MarkdownFormatter::default()).expect("加载模板失败");
+    let blocks = LuaFileParser {}.parse_str(SOURCE);
+    let output = formatter.format(&blocks).expect("渲染失败");
+    assert_eq!(
+        output,
+        "# API (1)\n## add\n`function add(a, b)` 两数相加\n* 0. a: number\n* 1. b: number\n=> number 和\n"
+    );
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+/// 没有 block 模板时每个文档块回退到内置的 Markdown 输出
+#[test]
+fn missing_block_template_falls_back_to_markdown() {
+    let dir = template_dir("fallback", &[(TemplateFormatter::INDEX_TEMPLATE, "<!-- api -->\n{{ content }}")]);
+    let formatter = TemplateFormatter::new(&dir, MarkdownFormatter::default()).expect("加载模板失败");
+    let blocks = LuaFileParser {}.parse_str(SOURCE);
+    let builtin = MarkdownFormatter::default().format(&blocks).expect("格式化失败");
+    assert_eq!(formatter.format(&blocks).expect("渲染失败"), format!("<!-- api -->\n{}", builtin));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+/// 回退时整个文件一次交给内置格式化器：@see 能链接到其他文档块，分组标题只出现一次
+#[test]
+fn fallback_keeps_see_links_and_group_headings() {
+    let dir = template_dir("fallback-links", &[(TemplateFormatter::INDEX_TEMPLATE, "{{ content }}")]);
+    let formatter = TemplateFormatter::new(&dir, MarkdownFormatter::default()).expect("加载模板失败");
+    let source = "\
+local M = {}
+--- @brief 压入元素
+--- @group 栈
+--- @see M.pop
+function M.push() end
+--- @brief 弹出元素
+--- @group 栈
+function M.pop() end
+";
+    let output = formatter.format(&LuaFileParser {}.parse_str(source)).expect("渲染失败");
+    assert!(output.contains("[`M.pop`](#mpop)"), "{}", output);
+    assert_eq!(output.matches("## 栈").count(), 1, "{}", output);
+    fs::remove_dir_all(&dir).unwrap();
+}
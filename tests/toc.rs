@@ -0,0 +1,46 @@
+//! 文件开头的函数目录与每个函数的锚点
+
+use todocument::file_parser::{unique_anchors, FileParser, LuaFileParser, MarkdownFormatter, OutputFileFormatter};
+
+/// 有三个函数的文件以三项目录开头，每项链接到对应函数的锚点
+#[test]
+fn file_toc_lists_every_function() {
+    let source = "\
+--- @brief a
+function a() end
+
+--- @brief b
+function b() end
+
+--- @brief c
+function c() end
+";
+    let blocks = LuaFileParser {}.parse_str(source);
+    let markdown = MarkdownFormatter::default().format(&blocks).expect("格式化失败");
+    assert!(markdown.starts_with("## Contents\n\n- [a](#a)\n- [b](#b)\n- [c](#c)\n\n"), "{}", markdown);
+    for name in ["a", "b", "c"] {
+        assert!(markdown.contains(&format!("### {}\n", name)));
+    }
+}
+
+/// 同名函数的锚点依次追加 `-1`、`-2`，目录中的每一项都指向各自的函数
+#[test]
+fn duplicate_names_get_numbered_anchors() {
+    let source = "\
+--- @brief 第一个
+function f() end
+
+--- @brief 第二个
+function f() end
+
+--- @brief 第三个
+function f() end
+
+--- @brief g
+function g() end
+";
+    let blocks = LuaFileParser {}.parse_str(source);
+    assert_eq!(unique_anchors(&blocks), vec!["f", "f-1", "f-2", "g"]);
+    let markdown = MarkdownFormatter::default().format(&blocks).expect("格式化失败");
+    assert!(markdown.contains("- [f](#f)\n- [f](#f-1)\n- [f](#f-2)\n- [g](#g)\n"), "{}", markdown);
+}
@@ -0,0 +1,43 @@
+//! 解析文档注释时产生的警告
+
+use todocument::file_parser::{FileParser, LuaFileParser};
+
+/// 解析 source 中的第一个文档块，返回其警告的 (code, message)
+fn warnings(source: &str) -> Vec<(&'static str, String)> {
+    let blocks = LuaFileParser {}.parse_str(source);
+    blocks[0].warnings.iter().map(|w| (w.code, w.message.clone())).collect()
+}
+
+/// 没有内容的 @throws / @error 是已知标签：给出 empty-tag 警告，不产生错误项
+#[test]
+fn empty_throws_is_not_an_unknown_tag() {
+    for tag in ["throws", "error"] {
+        let source = format!("--- @brief f\n--- @{}\nfunction f() end\n", tag);
+        assert_eq!(warnings(&source), vec![("empty-tag", format!("@{} 缺少内容", tag))]);
+        assert!(LuaFileParser {}.parse_str(&source)[0].errors.is_empty());
+    }
+}
+
+/// 没有内容的 @see 给出 empty-tag 警告，不产生空的链接
+#[test]
+fn empty_see_is_not_an_unknown_tag() {
+    let source = "--- @brief f\n--- @see\n--- @see g\nfunction f() end\n";
+    assert_eq!(warnings(source), vec![("empty-tag", "@see 缺少内容".to_string())]);
+    assert_eq!(LuaFileParser {}.parse_str(source)[0].see, vec!["g".to_string()]);
+}
+
+/// 没有内容的 @author 给出 empty-tag 警告，不影响其余作者的合并
+#[test]
+fn empty_author_is_not_an_unknown_tag() {
+    let source = "--- @brief f\n--- @author a\n--- @author\n--- @author b\nfunction f() end\n";
+    assert_eq!(warnings(source), vec![("empty-tag", "@author 缺少内容".to_string())]);
+    assert_eq!(LuaFileParser {}.parse_str(source)[0].author.as_deref(), Some("a, b"));
+}
+
+/// 没有内容的 @alias 给出 empty-tag 警告，不生成空的锚点与别名
+#[test]
+fn empty_alias_is_skipped() {
+    let source = "--- @brief f\n--- @alias\nfunction f() end\n";
+    assert_eq!(warnings(source), vec![("empty-tag", "@alias 缺少内容".to_string())]);
+    assert!(LuaFileParser {}.parse_str(source)[0].aliases.is_empty());
+}